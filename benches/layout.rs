@@ -0,0 +1,47 @@
+use buckle::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Autos nested inside each other, each hugging the next, with a label at
+/// the bottom. The worst case for measuring, since every level's size
+/// depends on everything below it.
+fn nested(depth: usize) -> Auto {
+    let mut view = Auto::vertical()
+        .width(ContainerSizing::Hug)
+        .height(ContainerSizing::Hug)
+        .push(Label::new("leaf"));
+    for i in 0..depth {
+        let auto = if i % 2 == 0 {
+            Auto::horizontal()
+        } else {
+            Auto::vertical()
+        };
+        view = auto
+            .width(ContainerSizing::Hug)
+            .height(ContainerSizing::Hug)
+            .push(view)
+            .push(Label::new("sibling"));
+    }
+    view
+}
+
+fn deep_trees(c: &mut Criterion) {
+    let dimensions = Dimensions::new(200, 60);
+    let mut group = c.benchmark_group("nested autos");
+    // Measured per level, so the throughput stays flat while each level is
+    // measured a fixed number of times
+    for depth in [100, 200, 400, 800] {
+        group.throughput(Throughput::Elements(depth as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &depth, |b, depth| {
+            b.iter(|| {
+                let view = nested(*depth);
+                let mut buffer = Buffer::new(dimensions.clone());
+                view.render(&Rect::new(Point::zero(), dimensions.clone()), &mut buffer);
+                buffer
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, deep_trees);
+criterion_main!(benches);
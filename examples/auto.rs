@@ -1,12 +1,13 @@
 use buckle::*;
 
-fn main() {
+fn main() -> Result<()> {
     let paragraph = "A reasonably long bit of multi-line text,
 which is used to demonstrate the rendering of text across 
 muliple lines. It does also display the behaviour of this 
 widget, which is to truncate the text, not wrap it.";
 
-    let mut terminal = Terminal::new();
+    Terminal::install_panic_hook();
+    let mut terminal = Terminal::new()?;
 
     let h_layout = Border::new(
         Stroke::SolidRounded,
@@ -14,14 +15,14 @@ widget, which is to truncate the text, not wrap it.";
             Auto::horizontal()
                 .layout(Layout::fixed(Align::Start, Align::Start, 1))
                 .height(ContainerSizing::Fixed(35))
-                .add(MultilineText::new(paragraph).width(ContainerSizing::Fixed(80)))
-                .add(
+                .push(MultilineText::new(paragraph).width(ContainerSizing::Fixed(80)))
+                .push(
                     Label::new("Fill")
                         .width(ContainerSizing::Fill)
                         .height(ContainerSizing::Fill),
                 )
-                .add(Label::new("Hug").height(ContainerSizing::Fill))
-                .add(
+                .push(Label::new("Hug").height(ContainerSizing::Fill))
+                .push(
                     Label::new("Fixed")
                         .width(ContainerSizing::Fixed(20))
                         .height(ContainerSizing::Fill),
@@ -32,19 +33,19 @@ widget, which is to truncate the text, not wrap it.";
 
     let v_layout = Border::new(
         Stroke::SolidRounded,
-        Auto::vertical().add(h_layout).add(
+        Auto::vertical().push(h_layout).push(
             Auto::vertical()
                 .height(ContainerSizing::Fixed(4))
-                .add(Label::new("Before"))
-                .add(Rule::new(Dir::Horizontal))
-                .add(Label::new("After"))
-                .add(Label::new("Another After"))
-                .add(Label::new("More After"))
-                .add(Label::new("Final After")),
+                .push(Label::new("Before"))
+                .push(Rule::new(Dir::Horizontal))
+                .push(Label::new("After"))
+                .push(Label::new("Another After"))
+                .push(Label::new("More After"))
+                .push(Label::new("Final After")),
         ),
     );
 
-    let mut buffer = terminal.prepare_buffer();
+    let mut buffer = terminal.prepare_buffer()?;
     v_layout.render(
         &Rect {
             origin: Point::zero(),
@@ -53,7 +54,11 @@ widget, which is to truncate the text, not wrap it.";
         &mut buffer,
     );
 
-    terminal.update(buffer);
+    terminal.update(buffer)?;
 
-    loop {}
+    loop {
+        if let TerminalEvent::Key(_) = terminal.next_event()? {
+            return Ok(());
+        }
+    }
 }
@@ -1,15 +1,16 @@
 use buckle::*;
 
-const ONE: &'static str = "─┐ 
+const ONE: &str = "─┐ 
  │ 
 ─┴─";
 
-const TWO: &'static str = "┌─┐
+const TWO: &str = "┌─┐
 ┌─┘
 └─┘";
 
-fn main() {
-    let mut terminal = Terminal::new();
+fn main() -> Result<()> {
+    Terminal::install_panic_hook();
+    let mut terminal = Terminal::new()?;
 
     let layout = Auto::new(
         Dir::Horizontal,
@@ -17,18 +18,18 @@ fn main() {
         ContainerSizing::Fill,
         ContainerSizing::Fill,
     )
-    .add(
+    .push(
         MultilineText::new(ONE)
             .width(ContainerSizing::Fixed(3))
             .height(ContainerSizing::Fixed(3)),
     )
-    .add(
+    .push(
         MultilineText::new(TWO)
             .width(ContainerSizing::Fixed(3))
             .height(ContainerSizing::Fixed(3)),
     );
 
-    let mut buffer = terminal.prepare_buffer();
+    let mut buffer = terminal.prepare_buffer()?;
     layout.render(
         &Rect {
             origin: Point::zero(),
@@ -37,7 +38,11 @@ fn main() {
         &mut buffer,
     );
 
-    terminal.update(buffer);
+    terminal.update(buffer)?;
 
-    loop {}
+    loop {
+        if let TerminalEvent::Key(_) = terminal.next_event()? {
+            return Ok(());
+        }
+    }
 }
@@ -1,8 +1,9 @@
 use buckle::*;
 
-fn main() {
-    let mut terminal = Terminal::new();
-    let mut buffer = terminal.prepare_buffer();
+fn main() -> Result<()> {
+    Terminal::install_panic_hook();
+    let mut terminal = Terminal::new()?;
+    let mut buffer = terminal.prepare_buffer()?;
     layout().render(
         &Rect {
             origin: Point::zero(),
@@ -11,96 +12,100 @@ fn main() {
         &mut buffer,
     );
 
-    terminal.update(buffer);
+    terminal.update(buffer)?;
 
-    loop {}
+    loop {
+        if let TerminalEvent::Key(_) = terminal.next_event()? {
+            return Ok(());
+        }
+    }
 }
 
 fn layout() -> PinBoard {
-    PinBoard::new(Sizing::Fill, Sizing::Fill).add(
+    PinBoard::new(Sizing::Fill, Sizing::Fill).push(
         PinOrigin::TopLeft(Point::zero()),
         Auto::horizontal()
-            .add(
+            .push(
                 Auto::vertical()
                     // .layout(Layout::fixed(Align::Start, Align::Start, 1))
                     .width(ContainerSizing::Fixed(22))
-                    .add(
+                    .push(
                         Styled::new(Padding::all(
                             1,
                             Auto::vertical()
                                 .height(ContainerSizing::Hug)
-                                .add(Label::new(" ◀ ▶ PATTERNS").width(ContainerSizing::Fill))
-                                .add(Label::new(" ▲ ▼ INDEX: 001").width(ContainerSizing::Fill)),
+                                .push(Label::new(" ◀ ▶ PATTERNS").width(ContainerSizing::Fill))
+                                .push(Label::new(" ▲ ▼ INDEX: 001").width(ContainerSizing::Fill)),
                         ))
                         .background(Color::Green)
                         .foreground(Color::Black),
                     )
-                    .add(Padding::horizontal(1, BigNumbers::new(&144.10)))
-                    .add(Label::new(" POS: 00:00:00"))
-                    .add(Label::new(" SEQ: 00:00:00"))
-                    .add(Label::new(" STP: 00:00:00")),
+                    .push(Padding::horizontal(1, BigNumbers::new(&144.10)))
+                    .push(Label::new(" POS: 00:00:00"))
+                    .push(Label::new(" SEQ: 00:00:00"))
+                    .push(Label::new(" STP: 00:00:00")),
             )
-            .add(Border::new(
+            .push(Border::new(
                 Stroke::Solid,
                 Auto::vertical()
-                    .add(
+                    .push(
                         Auto::horizontal()
                             .height(ContainerSizing::Hug)
                             .layout(Layout::fixed(Align::Start, Align::Start, 1))
-                            .add(Label::new("  # "))
-                            .add(Label::new("COM"))
-                            .add(Label::new("TAR"))
-                            .add(Label::new("FRQ"))
-                            .add(Rule::new(Dir::Horizontal))
-                            .add_each(1..=16, |i| Label::new(&format!("{:->3}", i))),
+                            .push(Label::new("  # "))
+                            .push(Label::new("COM"))
+                            .push(Label::new("TAR"))
+                            .push(Label::new("FRQ"))
+                            .push(Rule::new(Dir::Horizontal))
+                            .push_each(1..=16, |i| Label::new(format!("{:->3}", i))),
                     )
-                    .add(Rule::new(Dir::Horizontal))
-                    .add_each(1..=8, |i| {
+                    .push(Rule::new(Dir::Horizontal))
+                    .push_each(1..=8, |i| {
                         Auto::horizontal()
                             .height(ContainerSizing::Hug)
                             .layout(Layout::fixed(Align::Start, Align::Start, 1))
-                            .add(Label::new(&format!(" {:->3}", i)))
-                            .add(Label::new("···"))
-                            .add(Label::new("···"))
-                            .add(Label::new("···"))
-                            .add(Rule::new(Dir::Vertical))
-                            .add_each(1..=16, |i| Label::new("···"))
+                            .push(Label::new(format!(" {:->3}", i)))
+                            .push(Label::new("···"))
+                            .push(Label::new("···"))
+                            .push(Label::new("···"))
+                            .push(Rule::new(Dir::Vertical))
+                            .push_each(1..=16, |_| Label::new("···"))
                     }),
             )),
     )
 }
 
-const ZERO: &'static str = "┌─┐
+const ZERO: &str = "┌─┐
 │/│
 └─┘";
-const ONE: &'static str = "─┐ 
+const ONE: &str = "─┐ 
  │ 
 ─┴─";
-const TWO: &'static str = "┌─┐
+const TWO: &str = "┌─┐
 ┌─┘
 └─┘";
-const THREE: &'static str = "┌─┐
+const THREE: &str = "┌─┐
  ─┤
 └─┘";
-const FOUR: &'static str = "┌ ┐ 
+const FOUR: &str = "┌ ┐ 
 └─┤ 
   ┘";
-const FIVE: &'static str = "┌─┐
+const FIVE: &str = "┌─┐
 └─┐
 └─┘";
-const SIX: &'static str = "┌─┐
+const SIX: &str = "┌─┐
 ├─┐
 └─┘";
-const SEVEN: &'static str = "┌─┐
+const SEVEN: &str = "┌─┐
   │
   ┘";
-const EIGHT: &'static str = "┌─┐
+const EIGHT: &str = "┌─┐
 ├─┤
 └─┘";
-const NINE: &'static str = "┌─┐
+const NINE: &str = "┌─┐
 └─┤
   ┘";
-const DECIMAL: &'static str = " 
+const DECIMAL: &str = " 
  
 °";
 
@@ -116,18 +121,18 @@ impl BigNumbers {
         let formatted = format!("{:.2}", value);
         for i in formatted.chars() {
             layout = match i {
-                '0' => layout.add(MultilineText::new(ZERO)),
-                '1' => layout.add(MultilineText::new(ONE)),
-                '2' => layout.add(MultilineText::new(TWO)),
-                '3' => layout.add(MultilineText::new(THREE)),
-                '4' => layout.add(MultilineText::new(FOUR)),
-                '5' => layout.add(MultilineText::new(FIVE)),
-                '6' => layout.add(MultilineText::new(SIX)),
-                '7' => layout.add(MultilineText::new(SEVEN)),
-                '8' => layout.add(MultilineText::new(EIGHT)),
-                '9' => layout.add(MultilineText::new(NINE)),
-                '.' => layout.add(MultilineText::new(DECIMAL)),
-                _ => layout.add(MultilineText::new(ZERO)),
+                '0' => layout.push(MultilineText::new(ZERO)),
+                '1' => layout.push(MultilineText::new(ONE)),
+                '2' => layout.push(MultilineText::new(TWO)),
+                '3' => layout.push(MultilineText::new(THREE)),
+                '4' => layout.push(MultilineText::new(FOUR)),
+                '5' => layout.push(MultilineText::new(FIVE)),
+                '6' => layout.push(MultilineText::new(SIX)),
+                '7' => layout.push(MultilineText::new(SEVEN)),
+                '8' => layout.push(MultilineText::new(EIGHT)),
+                '9' => layout.push(MultilineText::new(NINE)),
+                '.' => layout.push(MultilineText::new(DECIMAL)),
+                _ => layout.push(MultilineText::new(ZERO)),
             };
         }
         Self { layout }
@@ -136,10 +141,10 @@ impl BigNumbers {
 
 impl View for BigNumbers {
     fn sizing(&self, bounds: &Dimensions) -> Constraints {
-        self.layout.sizing(&bounds)
+        self.layout.sizing(bounds)
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
-        self.layout.render(&within, buffer);
+        self.layout.render(within, buffer);
     }
 }
@@ -0,0 +1,63 @@
+use buckle::*;
+
+struct Counter {
+    count: i64,
+}
+
+enum Message {
+    Increment,
+    Decrement,
+    Quit,
+}
+
+impl App for Counter {
+    type Message = Message;
+
+    fn event(&self, event: TerminalEvent) -> Option<Message> {
+        match event {
+            TerminalEvent::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) => match code {
+                KeyCode::Up | KeyCode::Char('+') => Some(Message::Increment),
+                KeyCode::Down | KeyCode::Char('-') => Some(Message::Decrement),
+                KeyCode::Esc | KeyCode::Char('q') => Some(Message::Quit),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, message: Message) -> Update {
+        match message {
+            Message::Increment => self.count += 1,
+            Message::Decrement => self.count -= 1,
+            Message::Quit => return Update::Quit,
+        }
+        Update::Changed
+    }
+
+    fn view(&self) -> impl View {
+        PinBoard::new(Sizing::Fill, Sizing::Fill).push(
+            PinOrigin::Center,
+            Border::new(
+                Stroke::SolidRounded,
+                Padding::horizontal(
+                    1,
+                    Auto::vertical()
+                        .width(ContainerSizing::Hug)
+                        .height(ContainerSizing::Hug)
+                        .push(Label::new(format!("Count: {}", self.count)))
+                        .push(Label::new("↑/↓ to change, q to quit")),
+                ),
+            ),
+        )
+    }
+}
+
+fn main() -> Result<()> {
+    Terminal::install_panic_hook();
+    run(Counter { count: 0 })?;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use buckle::*;
+
+const FILES: [(&str, &str, &str); 4] = [
+    ("Cargo.toml", "toml", "312 B"),
+    ("README.md", "markdown", "1.2 KB"),
+    ("src/layouts/grid.rs", "rust", "5.8 KB"),
+    ("LICENSE.md", "markdown", "1.1 KB"),
+];
+
+fn main() -> Result<()> {
+    Terminal::install_panic_hook();
+    let mut terminal = Terminal::new()?;
+
+    let mut grid = Grid::new(
+        vec![
+            ContainerSizing::Hug,
+            ContainerSizing::Fill,
+            ContainerSizing::Hug,
+        ],
+        vec![ContainerSizing::Fixed(1); FILES.len() + 2],
+    )
+    .push(0, 0, Label::new("NAME "))
+    .push(1, 0, Label::new("TYPE"))
+    .push(2, 0, Label::new("SIZE"))
+    .span(0, 1, 3, 1, Rule::new(Dir::Horizontal));
+    for (i, (name, kind, size)) in FILES.iter().enumerate() {
+        grid = grid
+            .push(0, i + 2, Label::new(format!("{} ", name)))
+            .push(1, i + 2, Label::new(*kind))
+            .push(2, i + 2, Label::new(*size));
+    }
+
+    terminal.draw(&Border::new(Stroke::Solid, grid))?;
+
+    loop {
+        if let TerminalEvent::Key(_) = terminal.next_event()? {
+            return Ok(());
+        }
+    }
+}
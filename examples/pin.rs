@@ -1,45 +1,46 @@
 use buckle::*;
 
-fn main() {
-    let mut terminal = Terminal::new();
+fn main() -> Result<()> {
+    Terminal::install_panic_hook();
+    let mut terminal = Terminal::new()?;
 
     let layout = Border::new(
         Stroke::Solid,
         Styled::new(
             PinBoard::new(Sizing::Fill, Sizing::Fill)
-                .add(PinOrigin::TopLeft(Point::zero()), Label::new("Top Left"))
-                .add(
+                .push(PinOrigin::TopLeft(Point::zero()), Label::new("Top Left"))
+                .push(
                     PinOrigin::TopLeft(Point::new(10, 4)),
                     Label::new("Top Left Offset"),
                 )
-                .add(PinOrigin::TopRight(Point::zero()), Label::new("Top Right"))
-                .add(
+                .push(PinOrigin::TopRight(Point::zero()), Label::new("Top Right"))
+                .push(
                     PinOrigin::TopRight(Point::new(10, 4)),
                     Label::new("Top Right Offset"),
                 )
-                .add(
+                .push(
                     PinOrigin::BottomLeft(Point::zero()),
                     Label::new("Bottom Left"),
                 )
-                .add(
+                .push(
                     PinOrigin::BottomLeft(Point::new(10, 4)),
                     Label::new("Bottom Left Offset"),
                 )
-                .add(
+                .push(
                     PinOrigin::BottomRight(Point::zero()),
                     Label::new("Bottom Right"),
                 )
-                .add(
+                .push(
                     PinOrigin::BottomRight(Point::new(10, 4)),
                     Label::new("Bottom Right Offset"),
                 )
-                .add(PinOrigin::Center, Label::new("Center")),
+                .push(PinOrigin::Center, Label::new("Center")),
         )
         .background(Color::Grey)
         .foreground(Color::Black),
     );
 
-    let mut buffer = terminal.prepare_buffer();
+    let mut buffer = terminal.prepare_buffer()?;
     layout.render(
         &Rect {
             origin: Point::new(10, 5),
@@ -51,7 +52,11 @@ fn main() {
         &mut buffer,
     );
 
-    terminal.update(buffer);
+    terminal.update(buffer)?;
 
-    loop {}
+    loop {
+        if let TerminalEvent::Key(_) = terminal.next_event()? {
+            return Ok(());
+        }
+    }
 }
@@ -1,24 +1,25 @@
 use buckle::*;
 
-fn main() {
+fn main() -> Result<()> {
     let layout = ScrollBox::vertical(4)
-        .add(Label::new("One"))
-        .add(Label::new("Two"))
-        .add(Label::new("Three"))
-        .add(Label::new("Four"))
-        .add(Label::new("Five"))
-        .add(Label::new("Six"))
-        .add(Label::new("Seven"))
-        .add(Label::new("Eight"))
-        .add(Label::new("Nine"))
-        .add(Label::new("Ten"))
-        .add(Border::new(
+        .push(Label::new("One"))
+        .push(Label::new("Two"))
+        .push(Label::new("Three"))
+        .push(Label::new("Four"))
+        .push(Label::new("Five"))
+        .push(Label::new("Six"))
+        .push(Label::new("Seven"))
+        .push(Label::new("Eight"))
+        .push(Label::new("Nine"))
+        .push(Label::new("Ten"))
+        .push(Border::new(
             Stroke::Solid,
             Auto::vertical().height(ContainerSizing::Fixed(300)),
         ));
 
-    let mut terminal = Terminal::new();
-    let mut buffer = terminal.prepare_buffer();
+    Terminal::install_panic_hook();
+    let mut terminal = Terminal::new()?;
+    let mut buffer = terminal.prepare_buffer()?;
     layout.render(
         &Rect {
             origin: Point::zero(),
@@ -27,7 +28,11 @@ fn main() {
         &mut buffer,
     );
 
-    terminal.update(buffer);
+    terminal.update(buffer)?;
 
-    loop {}
+    loop {
+        if let TerminalEvent::Key(_) = terminal.next_event()? {
+            return Ok(());
+        }
+    }
 }
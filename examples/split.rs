@@ -0,0 +1,86 @@
+use buckle::*;
+
+struct Panes {
+    split: SplitState,
+    divider: usize,
+    size: Dimensions,
+}
+
+enum Message {
+    Mouse(MouseEvent),
+    Nudge(isize),
+    Select,
+    Resize(Dimensions),
+    Quit,
+}
+
+impl Panes {
+    fn area(&self) -> Rect {
+        Rect::new(Point::zero(), self.size.clone())
+    }
+}
+
+impl App for Panes {
+    type Message = Message;
+
+    fn event(&self, event: TerminalEvent) -> Option<Message> {
+        match event {
+            TerminalEvent::Mouse(mouse) => Some(Message::Mouse(mouse)),
+            TerminalEvent::Resize(size) => Some(Message::Resize(size)),
+            TerminalEvent::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) => match code {
+                KeyCode::Left => Some(Message::Nudge(-1)),
+                KeyCode::Right => Some(Message::Nudge(1)),
+                KeyCode::Tab => Some(Message::Select),
+                KeyCode::Esc | KeyCode::Char('q') => Some(Message::Quit),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, message: Message) -> Update {
+        let area = self.area();
+        let changed = match message {
+            Message::Mouse(mouse) => self.split.mouse(&mouse, &area),
+            Message::Nudge(amount) => self.split.nudge(self.divider, amount, &area),
+            Message::Select => {
+                self.divider = (self.divider + 1) % 2;
+                true
+            }
+            Message::Resize(size) => {
+                self.size = size;
+                false
+            }
+            Message::Quit => return Update::Quit,
+        };
+        if changed {
+            Update::Changed
+        } else {
+            Update::Unchanged
+        }
+    }
+
+    fn view(&self) -> impl View {
+        let pane =
+            |name: &str| Padding::horizontal(1, Label::new(name).width(ContainerSizing::Fill));
+        Split::new(&self.split)
+            .push(pane("Drag the dividers"))
+            .push(pane(&format!("←/→ move divider {}", self.divider + 1)))
+            .push(pane("Tab to switch, q to quit"))
+    }
+}
+
+fn main() -> Result<()> {
+    let (columns, rows) = crossterm::terminal::size()?;
+    Terminal::install_panic_hook();
+    run(Panes {
+        split: SplitState::horizontal(3),
+        divider: 0,
+        size: Dimensions::new(columns as usize, rows as usize),
+    })?;
+    Ok(())
+}
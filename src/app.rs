@@ -0,0 +1,145 @@
+use crate::backend::Backend;
+use crate::error::Result;
+use crate::terminal::{Terminal, TerminalEvent};
+use crate::views::View;
+
+/// An application built around a model, in the style of Elm: input events
+/// are turned into messages, messages update the model, and the view is
+/// rebuilt from the model whenever it changes.
+pub trait App {
+    type Message;
+
+    /// Maps an input event to a message. Events which map to `None` are
+    /// ignored.
+    fn event(&self, event: TerminalEvent) -> Option<Self::Message>;
+
+    fn update(&mut self, message: Self::Message) -> Update;
+
+    fn view(&self) -> impl View;
+}
+
+/// What the runtime should do after a message has been handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// The model changed, so the view is rendered again.
+    Changed,
+    /// Nothing visible changed, so rendering is skipped.
+    Unchanged,
+    /// Stop the runtime and hand the app back.
+    Quit,
+}
+
+/// Runs the app against stdout until it quits, then restores the terminal
+/// and returns the app.
+pub fn run<A: App>(mut app: A) -> Result<A> {
+    let mut terminal = Terminal::new()?;
+    run_with(&mut terminal, &mut app, terminal_event)?;
+    Ok(app)
+}
+
+fn terminal_event() -> Result<TerminalEvent> {
+    Ok(crossterm::event::read()?.into())
+}
+
+/// Runs the app on any terminal, pulling events from the provided source.
+/// Useful for driving an app headlessly, e.g. with a `MemoryBackend`.
+pub fn run_with<A, B, E>(terminal: &mut Terminal<B>, app: &mut A, mut events: E) -> Result<()>
+where
+    A: App,
+    B: Backend,
+    E: FnMut() -> Result<TerminalEvent>,
+{
+    terminal.draw(&app.view())?;
+
+    loop {
+        let event = events()?;
+        if let TerminalEvent::Resize(_) = event {
+            // Repaints in full if the size changed since the last draw.
+            terminal.draw(&app.view())?;
+        }
+
+        if let Some(message) = app.event(event) {
+            match app.update(message) {
+                Update::Changed => terminal.draw(&app.view())?,
+                Update::Unchanged => (),
+                Update::Quit => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod runtime_tests {
+    use super::*;
+    use crate::backend::MemoryBackend;
+    use crate::terminal::{KeyCode, KeyEvent, KeyModifiers};
+    use crate::values::Dimensions;
+    use crate::views::Label;
+
+    struct Echo {
+        last: char,
+    }
+
+    impl App for Echo {
+        type Message = char;
+
+        fn event(&self, event: TerminalEvent) -> Option<char> {
+            match event {
+                TerminalEvent::Key(KeyEvent {
+                    code: KeyCode::Char(c),
+                    ..
+                }) => Some(c),
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, message: char) -> Update {
+            if message == 'q' {
+                Update::Quit
+            } else {
+                self.last = message;
+                Update::Changed
+            }
+        }
+
+        fn view(&self) -> impl View {
+            Label::new(self.last.to_string())
+        }
+    }
+
+    fn key(c: char) -> TerminalEvent {
+        TerminalEvent::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn renders_after_each_update() {
+        let mut terminal =
+            Terminal::with_backend(MemoryBackend::new(Dimensions::new(3, 1))).unwrap();
+        let mut app = Echo { last: '-' };
+        let mut events = vec![key('q'), TerminalEvent::FocusLost, key('b'), key('a')];
+        run_with(&mut terminal, &mut app, || Ok(events.pop().unwrap())).unwrap();
+
+        assert_eq!('b', app.last);
+        assert_eq!("b", terminal.backend().buffer().cells[0][0].content);
+    }
+
+    #[test]
+    fn redraws_before_resize_event() {
+        let mut terminal =
+            Terminal::with_backend(MemoryBackend::new(Dimensions::new(3, 1))).unwrap();
+        terminal.backend_mut().resize(Dimensions::new(4, 2));
+        let mut app = Echo { last: '-' };
+        let mut events = vec![
+            key('q'),
+            TerminalEvent::Resize(Dimensions::new(4, 2)),
+            key('a'),
+        ];
+        run_with(&mut terminal, &mut app, || Ok(events.pop().unwrap())).unwrap();
+
+        assert_eq!(
+            Dimensions::new(4, 2),
+            terminal.backend().buffer().dimensions
+        );
+        assert_eq!("a", terminal.backend().buffer().cells[0][0].content);
+    }
+}
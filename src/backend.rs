@@ -0,0 +1,256 @@
+use crate::buffer::{Buffer, Cell};
+use crate::error::Result;
+use crate::values::{Dimensions, Point};
+use crossterm::{cursor, event, execute, queue, style, terminal};
+use std::io::{self, stdout, Stdout, Write};
+
+/// The output target a `Terminal` draws into. The terminal does the diffing,
+/// so a backend only ever sees the cells which actually changed.
+pub trait Backend {
+    fn size(&self) -> Result<Dimensions>;
+    fn move_cursor(&mut self, to: &Point) -> Result<()>;
+    /// Writes a cell at the cursor, advancing it by one column.
+    fn write_cell(&mut self, cell: &Cell) -> Result<()>;
+    fn flush(&mut self) -> Result<()>;
+    fn clear(&mut self) -> Result<()>;
+
+    /// Called once when the owning `Terminal` is created.
+    fn setup(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when the owning `Terminal` is dropped.
+    fn restore(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn draw(&mut self, changes: &[(Point, Cell)]) -> Result<()> {
+        for (point, cell) in changes {
+            self.move_cursor(point)?;
+            self.write_cell(cell)?;
+        }
+        self.flush()
+    }
+}
+
+/// Draws to stdout, taking over the whole screen while it is active.
+pub struct CrosstermBackend {
+    stdout: Stdout,
+}
+
+impl Default for CrosstermBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrosstermBackend {
+    pub fn new() -> Self {
+        Self { stdout: stdout() }
+    }
+}
+
+impl Backend for CrosstermBackend {
+    fn size(&self) -> Result<Dimensions> {
+        let (cols, rows) = terminal::size()?;
+        Ok(Dimensions::new(cols as usize, rows as usize))
+    }
+
+    fn move_cursor(&mut self, to: &Point) -> Result<()> {
+        queue_move(&mut self.stdout, to)
+    }
+
+    fn write_cell(&mut self, cell: &Cell) -> Result<()> {
+        queue_cell(&mut self.stdout, cell)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.stdout.flush()?;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        execute!(self.stdout, terminal::Clear(terminal::ClearType::All))?;
+        Ok(())
+    }
+
+    fn setup(&mut self) -> Result<()> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            self.stdout,
+            terminal::EnterAlternateScreen,
+            cursor::Hide,
+            event::EnableMouseCapture,
+            event::EnableFocusChange,
+            event::EnableBracketedPaste
+        )?;
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        restore_stdout()?;
+        Ok(())
+    }
+}
+
+/// Puts stdout back the way `CrosstermBackend::setup` found it. Safe to call
+/// more than once, which happens when the panic hook runs before `Drop`.
+pub(crate) fn restore_stdout() -> io::Result<()> {
+    execute!(
+        stdout(),
+        event::DisableBracketedPaste,
+        event::DisableFocusChange,
+        event::DisableMouseCapture,
+        cursor::Show,
+        terminal::LeaveAlternateScreen
+    )?;
+    terminal::disable_raw_mode()
+}
+
+/// Writes ANSI escape sequences to any writer, such as a file, socket or
+/// pipe. There is no terminal to ask, so the size is fixed up front.
+pub struct WriterBackend<W: Write> {
+    writer: W,
+    dimensions: Dimensions,
+}
+
+impl<W: Write> WriterBackend<W> {
+    pub fn new(writer: W, dimensions: Dimensions) -> Self {
+        Self { writer, dimensions }
+    }
+
+    pub fn resize(&mut self, dimensions: Dimensions) {
+        self.dimensions = dimensions;
+    }
+
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Backend for WriterBackend<W> {
+    fn size(&self) -> Result<Dimensions> {
+        Ok(self.dimensions.clone())
+    }
+
+    fn move_cursor(&mut self, to: &Point) -> Result<()> {
+        queue_move(&mut self.writer, to)
+    }
+
+    fn write_cell(&mut self, cell: &Cell) -> Result<()> {
+        queue_cell(&mut self.writer, cell)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        execute!(self.writer, terminal::Clear(terminal::ClearType::All))?;
+        Ok(())
+    }
+}
+
+/// Keeps the screen contents in a `Buffer`, for inspecting what a terminal
+/// would show without one being attached.
+pub struct MemoryBackend {
+    buffer: Buffer,
+    cursor: Point,
+}
+
+impl MemoryBackend {
+    pub fn new(dimensions: Dimensions) -> Self {
+        Self {
+            buffer: Buffer::new(dimensions),
+            cursor: Point::zero(),
+        }
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn resize(&mut self, dimensions: Dimensions) {
+        self.buffer = Buffer::new(dimensions);
+        self.cursor = Point::zero();
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn size(&self) -> Result<Dimensions> {
+        Ok(self.buffer.dimensions.clone())
+    }
+
+    fn move_cursor(&mut self, to: &Point) -> Result<()> {
+        self.cursor = to.clone();
+        Ok(())
+    }
+
+    fn write_cell(&mut self, cell: &Cell) -> Result<()> {
+        if self.cursor.x < self.buffer.dimensions.width
+            && self.cursor.y < self.buffer.dimensions.height
+        {
+            self.buffer.cells[self.cursor.y][self.cursor.x] = cell.clone();
+        }
+        self.cursor.x += 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.buffer = Buffer::new(self.buffer.dimensions.clone());
+        Ok(())
+    }
+}
+
+fn queue_move<W: Write>(writer: &mut W, to: &Point) -> Result<()> {
+    queue!(writer, cursor::MoveTo(to.x as u16, to.y as u16))?;
+    Ok(())
+}
+
+fn queue_cell<W: Write>(writer: &mut W, cell: &Cell) -> Result<()> {
+    // The wide character to the left already covers this cell
+    if cell.is_continuation() {
+        return Ok(());
+    }
+
+    let content = if let Some(style) = cell.style {
+        style::StyledContent::new(style.style, cell.content.as_str())
+    } else {
+        style::style(cell.content.as_str())
+    };
+    queue!(writer, style::PrintStyledContent(content))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod memory_backend_tests {
+    use super::*;
+    use crate::terminal::Terminal;
+
+    #[test]
+    fn update_draws_changes() {
+        let mut terminal =
+            Terminal::with_backend(MemoryBackend::new(Dimensions::new(5, 2))).unwrap();
+        let mut buffer = terminal.prepare_buffer().unwrap();
+        buffer.draw_text(&Point::new(1, 1), "abc");
+        terminal.update(buffer.clone()).unwrap();
+        assert_eq!(&buffer, terminal.backend().buffer());
+    }
+
+    #[test]
+    fn update_rejects_mismatched_buffer() {
+        let mut terminal =
+            Terminal::with_backend(MemoryBackend::new(Dimensions::new(5, 2))).unwrap();
+        let buffer = Buffer::new(Dimensions::new(4, 2));
+        assert!(terminal.update(buffer).is_err());
+    }
+}
@@ -1,20 +1,52 @@
+use crate::spans::Span;
 use crate::styles::{Stroke, Style};
+use crate::text::{grapheme_width, graphemes};
 use crate::values::{Dimensions, Point, Rect};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Cell {
-    pub content: char,
+    /// A single grapheme cluster. Empty when the cell is covered by a wide
+    /// character in the cell to its left.
+    pub content: String,
     pub style: Option<Style>,
 }
 
 impl Cell {
+    pub fn blank() -> Self {
+        Self {
+            content: " ".to_string(),
+            style: None,
+        }
+    }
+
+    /// Whether this cell is the second half of a wide character.
+    pub fn is_continuation(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    /// The number of columns the content of this cell covers.
+    pub fn width(&self) -> usize {
+        grapheme_width(&self.content)
+    }
+
     /// An update that preserves the original background colour, unless the
     /// other style provides one.
-    fn update(&mut self, content: char, other: &Option<Style>) {
-        self.content = content;
-        match (self.style, other) {
-            (None, Some(_)) => self.style = other.clone(),
-            (Some(mut existing), Some(update)) => existing.update(&update),
+    fn update(&mut self, content: &str, other: &Option<Style>) {
+        self.content.clear();
+        self.content.push_str(content);
+        self.update_style(other);
+    }
+
+    fn update_char(&mut self, content: char, other: &Option<Style>) {
+        self.content.clear();
+        self.content.push(content);
+        self.update_style(other);
+    }
+
+    fn update_style(&mut self, other: &Option<Style>) {
+        match (&mut self.style, other) {
+            (None, Some(_)) => self.style = *other,
+            (Some(existing), Some(update)) => existing.update(update),
             _ => (),
         }
     }
@@ -50,11 +82,7 @@ pub const ARC_UP_RIGHT: char = '╰';
 
 impl Buffer {
     pub fn new(dimensions: Dimensions) -> Self {
-        let default = Cell {
-            content: ' ',
-            style: None,
-        };
-        let row = vec![default; dimensions.width];
+        let row = vec![Cell::blank(); dimensions.width];
         let cells = vec![row; dimensions.height];
         Buffer { dimensions, cells }
     }
@@ -94,7 +122,7 @@ impl Buffer {
                         x: at.x + x,
                         y: at.y + y,
                     },
-                    cell.content,
+                    &cell.content,
                     cell.style,
                 );
             }
@@ -108,42 +136,129 @@ impl Buffer {
         for row in self.cells.iter_mut() {
             row.truncate(to.x);
             row.drain(0..from.x);
+
+            // Don't leave half of a wide character at either edge
+            if let Some(first) = row.first_mut() {
+                if first.is_continuation() {
+                    first.content.push(' ');
+                }
+            }
+            if let Some(last) = row.last_mut() {
+                if last.width() > 1 {
+                    last.update_char(' ', &None);
+                }
+            }
         }
+        self.dimensions = Dimensions::new(
+            self.cells.first().map(|row| row.len()).unwrap_or(0),
+            self.cells.len(),
+        );
     }
 
-    // Draws a character at the specified point, with the optional styling.
-    pub fn draw_cell(&mut self, at: Point, char: char, style: Option<Style>) {
+    // Draws a cell at the specified point, replacing its contents and style.
+    pub fn draw_cell(&mut self, at: Point, content: &str, style: Option<Style>) {
         let cell = self.mut_cell(at.x, at.y);
-        cell.content = char;
+        cell.content.clear();
+        cell.content.push_str(content);
         cell.style = style;
     }
 
     pub fn draw_text(&mut self, at: &Point, text: &str) {
-        let pairs = (at.x..(text.len() + at.x)).zip(text.chars());
-        for (x, c) in pairs {
-            self.mut_cell(x, at.y).update(c, &None);
+        let mut x = at.x;
+        for (grapheme, width) in graphemes(text) {
+            if x + width > self.dimensions.width {
+                break;
+            }
+            self.draw_grapheme(x, at.y, grapheme, width, &None);
+            x += width;
+        }
+    }
+
+    /// Draws the spans one after the other, each with its own style merged
+    /// into whatever is already in the buffer.
+    pub fn draw_spans(&mut self, at: &Point, spans: &[Span]) {
+        let mut x = at.x;
+        for span in spans {
+            for (grapheme, width) in graphemes(&span.content) {
+                if x + width > self.dimensions.width {
+                    return;
+                }
+                self.draw_grapheme(x, at.y, grapheme, width, &span.style);
+                x += width;
+            }
         }
     }
 
     pub fn draw_multiline_text(&mut self, within: &Rect, text: &str) {
-        for (line_number, line) in text.lines().enumerate() {
+        self.draw_lines(within, text.lines());
+    }
+
+    /// Draws each line on its own row, clipping anything outside the rect.
+    pub fn draw_lines<I, S>(&mut self, within: &Rect, lines: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for (line_number, line) in lines.into_iter().enumerate() {
             if line_number < within.dimensions.height {
-                for (entry_number, entry) in line.chars().enumerate() {
-                    if entry_number < within.dimensions.width {
-                        self.mut_cell(
-                            within.origin.x + entry_number,
-                            within.origin.y + line_number,
-                        )
-                        .update(entry, &None);
+                let mut offset = 0;
+                for (grapheme, width) in graphemes(line.as_ref()) {
+                    if offset + width > within.dimensions.width {
+                        break;
                     }
+                    self.draw_grapheme(
+                        within.origin.x + offset,
+                        within.origin.y + line_number,
+                        grapheme,
+                        width,
+                        &None,
+                    );
+                    offset += width;
                 }
             }
         }
     }
 
+    /// Draws a grapheme cluster which covers `width` cells, marking the
+    /// second cell of a wide character as a continuation.
+    fn draw_grapheme(
+        &mut self,
+        x: usize,
+        y: usize,
+        grapheme: &str,
+        width: usize,
+        style: &Option<Style>,
+    ) {
+        self.split_wide(x, y);
+        if width > 1 && x + 1 < self.dimensions.width {
+            self.split_wide(x + 1, y);
+            self.mut_cell(x, y).update(grapheme, style);
+            self.mut_cell(x + 1, y).update("", style);
+        } else if width > 1 {
+            // Only half of it would be visible
+            self.mut_cell(x, y).update_char(' ', style);
+        } else {
+            self.mut_cell(x, y).update(grapheme, style);
+        }
+    }
+
+    /// Blanks out the other half of any wide character covering this cell,
+    /// since it's about to be overwritten.
+    fn split_wide(&mut self, x: usize, y: usize) {
+        let cell = self.mut_cell(x, y);
+        if cell.is_continuation() {
+            cell.content.push(' ');
+            if x > 0 {
+                self.mut_cell(x - 1, y).content = " ".to_string();
+            }
+        } else if cell.width() > 1 && x + 1 < self.dimensions.width {
+            self.mut_cell(x + 1, y).content = " ".to_string();
+        }
+    }
+
     pub fn draw_h_rule(&mut self, at: &Point, length: usize) {
         for x in at.x..(at.x + length) {
-            self.mut_cell(x, at.y).update(H_LINE, &None);
+            self.draw_char(x, at.y, H_LINE, &None);
         }
     }
 
@@ -156,23 +271,24 @@ impl Buffer {
     }
 
     pub fn draw_char(&mut self, x: usize, y: usize, content: char, style: &Option<Style>) {
-        self.mut_cell(x, y).update(content, style);
+        self.split_wide(x, y);
+        self.mut_cell(x, y).update_char(content, style);
     }
 
     pub fn draw_v_rule(&mut self, at: &Point, length: usize) {
         for y in at.y..(at.y + length) {
-            self.mut_cell(at.x, y).update(V_LINE, &None);
+            self.draw_char(at.x, y, V_LINE, &None);
         }
     }
 
     pub fn merge_style(&mut self, within: &Rect, style: &Style) {
         for row in within.origin.y..(within.origin.y + within.dimensions.height) {
             for col in within.origin.x..(within.origin.x + within.dimensions.width) {
-                let mut cell = self.mut_cell(col, row);
+                let cell = self.mut_cell(col, row);
                 if let Some(existing) = &mut cell.style {
                     existing.update(style)
                 } else {
-                    cell.style = Some(style.clone())
+                    cell.style = Some(*style)
                 }
             }
         }
@@ -182,7 +298,7 @@ impl Buffer {
         let char = char.unwrap_or(' ');
         for row in within.origin.y..(within.origin.y + within.dimensions.height) {
             for col in within.origin.x..(within.origin.x + within.dimensions.width) {
-                self.mut_cell(col, row).update(char, &Some(style));
+                self.draw_char(col, row, char, &Some(style));
             }
         }
     }
@@ -200,10 +316,10 @@ impl Buffer {
                 } else {
                     (DOWN_RIGHT, UP_RIGHT)
                 };
-                self.mut_cell(x, rect.origin.y).update(top, style);
-                self.mut_cell(x, y_inset).update(bottom, style);
+                self.draw_char(x, rect.origin.y, top, style);
+                self.draw_char(x, y_inset, bottom, style);
                 for y in (rect.origin.y + 1)..y_inset {
-                    self.mut_cell(x, y).update(V_LINE, style);
+                    self.draw_char(x, y, V_LINE, style);
                 }
             } else if x == last_inset {
                 let (top, bottom) = if let Stroke::SolidRounded = stroke {
@@ -211,15 +327,14 @@ impl Buffer {
                 } else {
                     (DOWN_LEFT, UP_LEFT)
                 };
-                self.mut_cell(x, rect.origin.y).update(top, style);
-                self.draw_cell(Point { x: x, y: y_inset }, bottom, style.clone());
-                self.mut_cell(x, y_inset).update(bottom, style);
+                self.draw_char(x, rect.origin.y, top, style);
+                self.draw_char(x, y_inset, bottom, style);
                 for y in (rect.origin.y + 1)..y_inset {
-                    self.mut_cell(x, y).update(V_LINE, style);
+                    self.draw_char(x, y, V_LINE, style);
                 }
             } else {
-                self.mut_cell(x, rect.origin.y).update(H_LINE, style);
-                self.mut_cell(x, y_inset).update(H_LINE, style);
+                self.draw_char(x, rect.origin.y, H_LINE, style);
+                self.draw_char(x, y_inset, H_LINE, style);
             }
         }
     }
@@ -228,3 +343,41 @@ impl Buffer {
         &self.cells[point.y][point.x]
     }
 }
+
+#[cfg(test)]
+mod wide_character_tests {
+    use super::*;
+
+    fn row(buffer: &Buffer) -> Vec<&str> {
+        buffer.cells[0].iter().map(|c| c.content.as_str()).collect()
+    }
+
+    #[test]
+    fn wide_characters_cover_two_cells() {
+        let mut buffer = Buffer::new(Dimensions::new(5, 1));
+        buffer.draw_text(&Point::zero(), "日本!");
+        assert_eq!(vec!["日", "", "本", "", "!"], row(&buffer));
+    }
+
+    #[test]
+    fn combining_marks_stay_in_one_cell() {
+        let mut buffer = Buffer::new(Dimensions::new(3, 1));
+        buffer.draw_text(&Point::zero(), "e\u{301}x");
+        assert_eq!(vec!["e\u{301}", "x", " "], row(&buffer));
+    }
+
+    #[test]
+    fn overwriting_half_blanks_the_other() {
+        let mut buffer = Buffer::new(Dimensions::new(4, 1));
+        buffer.draw_text(&Point::zero(), "日本");
+        buffer.draw_char(1, 0, V_LINE, &None);
+        assert_eq!(vec![" ", "│", "本", ""], row(&buffer));
+    }
+
+    #[test]
+    fn wide_character_clipped_at_edge() {
+        let mut buffer = Buffer::new(Dimensions::new(3, 1));
+        buffer.draw_multiline_text(&Rect::new_from_raw(0, 0, 3, 1), "日本");
+        assert_eq!(vec!["日", "", " "], row(&buffer));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::values::Dimensions;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Writing to or querying the terminal failed, e.g. because stdout is a
+    /// closed pipe.
+    Io(io::Error),
+    /// A buffer was passed to `Terminal::update` that doesn't match the size
+    /// of the terminal. Usually means a resize happened between preparing the
+    /// buffer and rendering it.
+    DimensionMismatch {
+        expected: Dimensions,
+        actual: Dimensions,
+    },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "terminal I/O failed: {}", err),
+            Error::DimensionMismatch { expected, actual } => write!(
+                f,
+                "buffer is {}x{} but the terminal is {}x{}",
+                actual.width, actual.height, expected.width, expected.height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::DimensionMismatch { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
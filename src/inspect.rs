@@ -0,0 +1,299 @@
+use crate::buffer::Buffer;
+use crate::spans::Span;
+use crate::styles::{Color, Stroke, Style};
+use crate::values::{Constraints, ContainerSizing, Dimensions, Rect, Sizing};
+use crate::views::View;
+use std::fmt;
+
+/// Where a view was placed by the layout, and the constraints it asked for,
+/// along with the same for each of its children. See `View::inspect`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutNode {
+    /// The view's type, without its module path.
+    pub name: String,
+    pub rect: Rect,
+    pub constraints: Constraints,
+    pub children: Vec<LayoutNode>,
+    /// Problems spotted with the layout of this view, like hugging children
+    /// which have no size of their own.
+    pub warnings: Vec<String>,
+}
+
+/// Bounds large enough that nothing is clamped by them, without risking
+/// overflow when sizes are added together.
+const UNBOUNDED: usize = u16::MAX as usize;
+
+impl LayoutNode {
+    /// A node for the view without any children.
+    pub fn of<V: View + ?Sized>(view: &V, within: &Rect) -> Self {
+        Self {
+            name: short_type_name(std::any::type_name::<V>()),
+            rect: within.clone(),
+            constraints: view.sizing(&within.dimensions),
+            children: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn children(mut self, children: Vec<LayoutNode>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn warn(mut self, warning: String) -> Self {
+        self.warnings.push(warning);
+        self
+    }
+
+    /// Warns if the view hugs its children on an axis, but would have no
+    /// size on it however much room it was given, e.g. because all of its
+    /// children fill.
+    pub fn check_hug<V: View + ?Sized>(
+        mut self,
+        view: &V,
+        width: &ContainerSizing,
+        height: &ContainerSizing,
+    ) -> Self {
+        if self.children.is_empty() {
+            return self;
+        }
+        let unbounded = view.sizing(&Dimensions::new(UNBOUNDED, UNBOUNDED));
+        for (axis, hugs, sizing) in [
+            ("width", width.hugs(), unbounded.width),
+            ("height", height.hugs(), unbounded.height),
+        ] {
+            if hugs && sizing == Sizing::Fixed(0) {
+                self = self.warn(format!(
+                    "hugs its {}, but none of its children have a preferred {}, so it collapses to nothing",
+                    axis, axis
+                ));
+            }
+        }
+        self
+    }
+
+    /// Every warning in the tree, each prefixed by the name of the view.
+    pub fn all_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        self.walk(&mut |node, _| {
+            for warning in &node.warnings {
+                warnings.push(format!("{} {}", node.name, warning));
+            }
+        });
+        warnings
+    }
+
+    /// Visits this node and all of its descendants, parents first.
+    pub fn walk<F: FnMut(&LayoutNode, usize)>(&self, visit: &mut F) {
+        self.walk_at(0, visit);
+    }
+
+    fn walk_at<F: FnMut(&LayoutNode, usize)>(&self, depth: usize, visit: &mut F) {
+        visit(self, depth);
+        for child in &self.children {
+            child.walk_at(depth + 1, visit);
+        }
+    }
+}
+
+/// Prints the tree with one node per line, indented by depth, e.g.
+/// `Label 2,0 5x1 (Fixed(5), Fixed(1))`.
+impl fmt::Display for LayoutNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Ok(());
+        self.walk(&mut |node, depth| {
+            if result.is_ok() {
+                result = writeln!(
+                    f,
+                    "{:indent$}{} {},{} {}x{} ({:?}, {:?})",
+                    "",
+                    node.name,
+                    node.rect.origin.x,
+                    node.rect.origin.y,
+                    node.rect.dimensions.width,
+                    node.rect.dimensions.height,
+                    node.constraints.width,
+                    node.constraints.height,
+                    indent = depth * 2
+                );
+            }
+            for warning in &node.warnings {
+                if result.is_ok() {
+                    result = writeln!(f, "{:indent$}! {}", "", warning, indent = depth * 2 + 2);
+                }
+            }
+        });
+        result
+    }
+}
+
+/// Strips the module paths from a type name, including those of any generic
+/// parameters, e.g. `buckle::layouts::Auto` becomes `Auto`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or(""));
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or(""));
+    short
+}
+
+/// Renders a view, then draws the outline and size of every rect in its
+/// layout over the top, to help track down views which end up the wrong
+/// size. Rects with no area can't be outlined, so only their size is
+/// drawn, in red, as are the sizes of views with warnings.
+pub struct DebugLayout {
+    item: Box<dyn View>,
+}
+
+impl DebugLayout {
+    pub fn new<V: View>(item: V) -> Self {
+        Self {
+            item: Box::new(item),
+        }
+    }
+}
+
+impl View for DebugLayout {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.item.sizing(bounds)
+    }
+
+    fn render(&self, within: &Rect, buffer: &mut Buffer) {
+        self.item.render(within, buffer);
+
+        let outline = Some(Style::new().foreground(Color::Magenta));
+        let collapsed = Style::new().foreground(Color::Red);
+        let width = buffer.dimensions.width;
+        let height = buffer.dimensions.height;
+
+        self.item.inspect(within).walk(&mut |node, _| {
+            let rect = &node.rect;
+            if rect.origin.x >= width || rect.origin.y >= height {
+                return;
+            }
+            let size = format!("{}x{}", rect.dimensions.width, rect.dimensions.height);
+            if rect.dimensions.width == 0
+                || rect.dimensions.height == 0
+                || !node.warnings.is_empty()
+            {
+                buffer.draw_spans(&rect.origin, &[Span::styled(&size, collapsed)]);
+                return;
+            }
+
+            let mut clipped = rect.clone();
+            clipped.dimensions.width = clipped.dimensions.width.min(width - rect.origin.x);
+            clipped.dimensions.height = clipped.dimensions.height.min(height - rect.origin.y);
+            if clipped.dimensions.width >= 2 && clipped.dimensions.height >= 2 {
+                buffer.draw_box(&clipped, &Stroke::Solid, &outline);
+            }
+            buffer.draw_spans(
+                &rect.origin,
+                &[Span {
+                    content: size,
+                    style: outline,
+                }],
+            );
+        });
+    }
+}
+
+#[cfg(test)]
+mod inspect_tests {
+    use super::*;
+    use crate::layouts::Auto;
+    use crate::testing::TestTerminal;
+    use crate::values::{Dir, Point, Sizing};
+    use crate::views::{Border, Label, Padding, Rule, Spacer};
+
+    fn layout() -> Auto {
+        Auto::horizontal()
+            .push(Border::new(Stroke::Solid, Label::new("ab")))
+            .push(Label::new("xyz"))
+            .push(Label::new("c").width(ContainerSizing::Fixed(0)))
+    }
+
+    #[test]
+    fn tree_of_rects() {
+        let tree = layout().inspect(&Rect::new(Point::zero(), Dimensions::new(10, 3)));
+        assert_eq!("Auto", tree.name);
+        assert_eq!(
+            Rect::new_from_raw(1, 1, 2, 1),
+            tree.children[0].children[0].rect
+        );
+        assert_eq!(
+            Constraints::new(Sizing::Fixed(0), Sizing::Fixed(1)),
+            tree.children[2].constraints
+        );
+        assert_eq!(
+            "\
+Auto 0,0 10x3 (Fill, Fill)
+  Border 0,0 4x3 (Fixed(4), Fixed(3))
+    Label 1,1 2x1 (Fixed(2), Fixed(1))
+  Label 4,0 3x1 (Fixed(3), Fixed(1))
+  Label 7,0 0x1 (Fixed(0), Fixed(1))
+",
+            tree.to_string()
+        );
+    }
+
+    #[test]
+    fn outlines_rects() {
+        let magenta = Style::new().foreground(Color::Magenta);
+        let red = Style::new().foreground(Color::Red);
+        TestTerminal::new(10, 3)
+            .render(&DebugLayout::new(layout()))
+            .assert_text(
+                "
+4x3┐3x10x1
+│2x1     │
+└──┘─────┘",
+            )
+            .assert_styles(
+                "
+mmmmmmmrrr
+mmmm     m
+mmmmmmmmmm",
+                &[('m', magenta), ('r', red)],
+            );
+    }
+
+    #[test]
+    fn warns_about_collapsed_hugs() {
+        fn hugging<V: View>(item: V) -> LayoutNode {
+            Auto::vertical()
+                .width(ContainerSizing::Hug)
+                .push(item)
+                .inspect(&Rect::new(Point::zero(), Dimensions::new(10, 3)))
+        }
+
+        let collapsed = hugging(Spacer::new());
+        assert_eq!(
+            vec!["Auto hugs its width, but none of its children have a preferred width, so it collapses to nothing"],
+            collapsed.all_warnings()
+        );
+        assert_eq!(Sizing::Fixed(0), collapsed.constraints.width);
+
+        let padded = hugging(Padding::horizontal(1, Spacer::new()));
+        assert!(padded.all_warnings().is_empty());
+        assert_eq!(Sizing::Fixed(2), padded.constraints.width);
+
+        let bordered = hugging(Border::new(Stroke::Solid, Rule::new(Dir::Horizontal)));
+        assert_eq!(Sizing::Fixed(3), bordered.constraints.width);
+    }
+
+    #[test]
+    fn short_names() {
+        assert_eq!(
+            "Vec<Box<dyn View>>",
+            short_type_name("alloc::vec::Vec<alloc::boxed::Box<dyn buckle::views::View>>")
+        );
+    }
+}
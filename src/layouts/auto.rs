@@ -1,11 +1,10 @@
 use crate::buffer::Buffer;
-use crate::layouts::auto_solver::solve;
+use crate::inspect::LayoutNode;
+use crate::layouts::auto_solver::{solve_aligned, Spacing};
+use crate::layouts::cache::SizingCache;
 use crate::values::*;
 use crate::views::View;
 
-/// A layout which positions it's children automatically based on their size
-/// and the constraints provided.
-
 /*
 Invariants that should hold:
 
@@ -13,12 +12,51 @@ Invariants that should hold:
 - Elements that start within the layout, but end without, will be truncated
 - Elements which start outside of the layout, will be clipped
 */
+
+/// A layout which positions it's children automatically based on their size
+/// and the constraints provided.
 pub struct Auto {
     dir: Dir,
     layout: Layout,
+    gaps: Gaps,
     width: ContainerSizing,
     height: ContainerSizing,
-    items: Vec<Box<dyn View>>,
+    items: Vec<Aligned>,
+    cache: SizingCache,
+}
+
+/// A child of an `Auto` layout with its own alignment on the cross axis,
+/// overriding the layout's, and a margin which is kept clear around it. In a
+/// reversed direction the margin is mirrored along the axis with the items.
+pub struct Aligned {
+    align: Option<Align>,
+    margin: Margin,
+    item: Box<dyn View>,
+}
+
+impl Aligned {
+    pub fn new<V: View>(item: V) -> Self {
+        Self {
+            align: None,
+            margin: Margin::default(),
+            item: Box::new(item),
+        }
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    pub fn margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// The item's constraints, grown to make room for the margin.
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.margin.grow(&self.item.sizing(bounds))
+    }
 }
 
 impl Auto {
@@ -26,9 +64,11 @@ impl Auto {
         Auto {
             dir,
             layout,
+            gaps: Gaps::default(),
             width,
             height,
             items: Vec::new(),
+            cache: SizingCache::default(),
         }
     }
 
@@ -55,6 +95,20 @@ impl Auto {
         self
     }
 
+    /// Adds padding at the ends, or sets the gaps between particular items,
+    /// on top of the layout's spacing.
+    pub fn gaps(mut self, gaps: Gaps) -> Self {
+        self.gaps = gaps;
+        self
+    }
+
+    /// Places the first item at the right, or the bottom, instead, e.g. to
+    /// stack a log up from the bottom.
+    pub fn reverse(mut self) -> Self {
+        self.dir = self.dir.reverse();
+        self
+    }
+
     pub fn width(mut self, width: ContainerSizing) -> Self {
         self.width = width;
         self
@@ -65,92 +119,167 @@ impl Auto {
         self
     }
 
-    pub fn add<V: View>(mut self, item: V) -> Self {
-        self.items.push(Box::new(item));
+    pub fn push<V: View>(mut self, item: V) -> Self {
+        self.items.push(Aligned::new(item));
         self
     }
 
-    pub fn add_each<I, F, V>(mut self, items: I, render: F) -> Self
+    /// Adds an item with its own alignment or margin.
+    pub fn push_aligned(mut self, item: Aligned) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    pub fn push_each<I, F, V>(mut self, items: I, render: F) -> Self
     where
         I: IntoIterator,
         V: View,
         F: Fn(&I::Item) -> V,
     {
         for item in items {
-            self.items.push(Box::new(render(&item)));
+            self.items.push(Aligned::new(render(&item)));
         }
         self
     }
 
     pub fn maybe_add<V: View>(mut self, check: bool, item: V) -> Self {
         if check {
-            self.items.push(Box::new(item));
+            self.items.push(Aligned::new(item));
         }
         self
     }
 }
 
-impl View for Auto {
-    fn sizing(&self, bounds: &Dimensions) -> Constraints {
-        // TODO:
-        // Look at optimising this by avoiding sizing calculations when the
-        // layout has a fixed or fill sizing in a particular axis.
-
-        let mut height = 0;
-        let mut width = 0;
-
-        match self.dir {
-            Dir::Horizontal => {
-                for item in &self.items {
-                    let constraints = item.sizing(&bounds);
-
-                    if let Sizing::Fixed(n) = constraints.width {
-                        width = (width + n).clamp(0, bounds.width)
-                    }
-
-                    if let Sizing::Fixed(n) = constraints.height {
-                        if n > height {
-                            height = n.clamp(0, bounds.height)
-                        }
-                    }
-                }
-            }
-            Dir::Vertical => {
-                for item in &self.items {
-                    let constraints = item.sizing(&bounds);
-
-                    if let Sizing::Fixed(n) = constraints.height {
-                        height = (height + n).clamp(0, bounds.height);
-                    }
-
-                    if let Sizing::Fixed(n) = constraints.width {
-                        if n > width {
-                            width = n.clamp(0, bounds.width)
-                        }
-                    }
+impl Auto {
+    /// The rect for each item.
+    fn arrange(&self, within: &Rect) -> Vec<Rect> {
+        let items: Vec<Constraints> = self
+            .items
+            .iter()
+            .map(|i| i.item.sizing(&within.dimensions))
+            .collect();
+        let overrides: Vec<(Option<Align>, Margin)> =
+            self.items.iter().map(|i| (i.align, i.margin)).collect();
+        solve_aligned(
+            &items,
+            &overrides,
+            &self.dir,
+            &self.layout,
+            &self.gaps,
+            within,
+        )
+    }
+
+    /// Measures the children to work out how large the layout is when
+    /// hugging them.
+    fn measure(&self, bounds: &Dimensions) -> Constraints {
+        // Measured along the width, like the solver
+        let rotate = self.dir.is_vertical();
+        let mut bounds = bounds.clone();
+        let mut items: Vec<Constraints> = self
+            .items
+            .iter()
+            .map(|item| {
+                let mut constraints = item.sizing(&bounds);
+                if rotate {
+                    constraints.rotate();
                 }
-            }
+                constraints
+            })
+            .collect();
+        if rotate {
+            bounds.rotate();
         }
 
-        Constraints {
-            width: self.width.simplify(width),
-            height: self.height.simplify(height),
-        }
+        let across = items
+            .iter()
+            .map(|constraints| constraints.preferred.height)
+            .max()
+            .unwrap_or(0)
+            .clamp(0, bounds.height);
+
+        // Items count towards hugging with their preferred size, and the
+        // spacers between them with their minimum
+        Spacing::new(&self.layout, &self.gaps).introduce(&mut items);
+        let along = items
+            .iter()
+            .map(|constraints| constraints.preferred.width)
+            .sum::<usize>()
+            .clamp(0, bounds.width);
+
+        let (width, height) = if rotate {
+            (across, along)
+        } else {
+            (along, across)
+        };
+        Constraints::around(&self.width, &self.height, Dimensions::new(width, height))
+    }
+}
+
+impl View for Auto {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.cache.measure(bounds, || self.measure(bounds))
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
-        let items: Vec<Constraints> = self
-            .items
-            .iter()
-            .map(|i| i.sizing(&within.dimensions))
-            .collect();
-        let layout = solve(&items, &self.dir, &self.layout, &within);
-        for (rect, item) in layout.iter().zip(&self.items) {
+        for (rect, item) in self.arrange(within).iter().zip(&self.items) {
             if rect.origin.x < within.origin.x + within.dimensions.width
                 && rect.origin.y < within.origin.y + within.dimensions.height
             {
-                item.render(&rect, buffer);
+                item.item.render(rect, buffer);
             }
         }
     }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        let children = self
+            .arrange(within)
+            .iter()
+            .zip(&self.items)
+            .map(|(rect, item)| item.item.inspect(rect))
+            .collect();
+        LayoutNode::of(self, within)
+            .children(children)
+            .check_hug(self, &self.width, &self.height)
+    }
+}
+
+#[cfg(test)]
+mod auto_tests {
+    use super::*;
+    use crate::styles::Stroke;
+    use crate::testing::TestTerminal;
+    use crate::views::{Border, Label};
+
+    #[test]
+    fn hugs_gaps() {
+        let auto = Auto::horizontal()
+            .width(ContainerSizing::Hug)
+            .layout(Layout::fixed(Align::Start, Align::Start, 1))
+            .gaps(Gaps::new().leading(1).trailing(1))
+            .push(Label::new("ab"))
+            .push(Label::new("cd"));
+        assert_eq!(Sizing::Fixed(7), auto.sizing(&Dimensions::new(20, 5)).width);
+    }
+
+    #[test]
+    fn hugs_preferred_sizes() {
+        let column = |text| Auto::vertical().push(Label::new(text));
+        let auto = Auto::horizontal()
+            .width(ContainerSizing::Hug)
+            .height(ContainerSizing::Hug)
+            .push(Border::new(Stroke::Solid, column("wide label")))
+            .push(Border::new(Stroke::Solid, column("thin")));
+        assert_eq!(
+            Constraints::new(Sizing::Fixed(18), Sizing::Fixed(3)),
+            auto.sizing(&Dimensions::new(40, 10))
+        );
+
+        TestTerminal::new(18, 3).render(&auto).assert_text(
+            "
+┌──────────┐┌────┐
+│wide label││thin│
+└──────────┘└────┘",
+        );
+    }
 }
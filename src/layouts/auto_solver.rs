@@ -2,19 +2,23 @@ use crate::values::*;
 
 // Accept a list of constraints and a rect
 // Figure out how to fit all the constrained elements in the
-pub fn solve(
-    items: &Vec<Constraints>,
+pub fn solve(items: &[Constraints], dir: &Dir, arrangement: &Layout, bounds: &Rect) -> Vec<Rect> {
+    solve_spaced(items, dir, arrangement, &Gaps::default(), bounds)
+}
+
+/// Solves the layout like `solve`, with padding at the ends and gaps between
+/// particular items on top of the arrangement's spacing.
+pub fn solve_spaced(
+    items: &[Constraints],
     dir: &Dir,
     arrangement: &Layout,
+    gaps: &Gaps,
     bounds: &Rect,
 ) -> Vec<Rect> {
-    let rotate = &Dir::Vertical == dir;
+    let rotate = dir.is_vertical();
     let mut arrangement = arrangement.clone();
     let mut bounds = bounds.clone();
-    let mut items = items.clone();
-    let mut spacing = Spacing::from_arrangement(&arrangement);
-    spacing.introduce(&mut items);
-
+    let mut items = items.to_vec();
     if rotate {
         bounds.rotate();
         arrangement.rotate();
@@ -23,56 +27,105 @@ pub fn solve(
         }
     }
 
+    // Spacers are introduced once rotated, so they run along the axis
+    let mut spacing = Spacing::new(&arrangement, gaps);
+    spacing.introduce(&mut items);
+
     let mut remaining_bounds = bounds.clone();
     let mut results: Vec<Rect> = Vec::new();
     let mut fills = Vec::new();
 
     for (i, constraints) in items.iter().enumerate() {
         let mut result = remaining_bounds.clone();
+        let mut derived_height = None;
 
         // Determine width
         match constraints.width {
             Sizing::Fixed(amt) => {
-                result.dimensions.width = amt.clamp(0, bounds.dimensions.width);
+                result.dimensions.width = amt.clamp(0, remaining_bounds.dimensions.width);
                 remaining_bounds.dimensions.width = remaining_bounds
                     .dimensions
                     .width
-                    .checked_sub(result.dimensions.width)
-                    .unwrap_or(0);
+                    .saturating_sub(result.dimensions.width);
+            }
+            Sizing::Percent(percent) => {
+                result.dimensions.width = (bounds.dimensions.width * percent.min(100) / 100)
+                    .clamp(0, remaining_bounds.dimensions.width);
+                remaining_bounds.dimensions.width -= result.dimensions.width;
+            }
+            Sizing::Fill => fills.push((i, Share::new(1, 0, usize::MAX))),
+            Sizing::Weighted(weight) => fills.push((i, Share::new(weight, 0, usize::MAX))),
+            Sizing::Bounded { min, max } => fills.push((i, Share::new(1, min, max))),
+            Sizing::Ratio { .. } => {
+                let fitted = constraints.resolve(&Dimensions::new(
+                    remaining_bounds.dimensions.width,
+                    bounds.dimensions.height,
+                ));
+                result.dimensions.width = fitted.width;
+                remaining_bounds.dimensions.width -= fitted.width;
+                derived_height = Some(fitted.height);
             }
-            Sizing::Fill => fills.push(i),
         }
 
         // Determine height
-        result.dimensions.height = match constraints.height {
-            Sizing::Fill => remaining_bounds.dimensions.height,
-            Sizing::Fixed(amt) => amt.clamp(0, bounds.dimensions.height),
+        result.dimensions.height = match (derived_height, constraints.height) {
+            (Some(height), _) => height,
+            (None, Sizing::Fixed(amt)) => amt.clamp(0, bounds.dimensions.height),
+            (None, height) => height.resolve(remaining_bounds.dimensions.height),
         };
 
         results.push(result);
     }
 
-    // Calculate the widths for the fills
-    let fill_width = remaining_bounds
-        .dimensions
-        .width
-        .checked_div(fills.len())
-        .unwrap_or(0);
+    // Calculate the widths for the fills, then give the spacers what the
+    // items' fills leave over
+    let (spacers, mut fills): (Vec<_>, Vec<_>) =
+        fills.into_iter().partition(|(i, _)| spacing.is_spacer(*i));
+    let mut space = remaining_bounds.dimensions.width;
+
+    // Where there's room, fills get at least their preferred size, so a
+    // container hugging its items gives each the size it measured
+    let preferred: usize = fills
+        .iter()
+        .map(|(i, share)| items[*i].preferred.width.clamp(share.min, share.max))
+        .sum();
+    if preferred <= space {
+        for (i, share) in fills.iter_mut() {
+            share.min = items[*i].preferred.width.clamp(share.min, share.max);
+        }
+    }
+    for fills in [fills, spacers] {
+        let shares: Vec<Share> = fills.iter().map(|(_, share)| *share).collect();
+        let fill_widths = distribute(space, &shares);
+        for ((i, _), width) in fills.iter().zip(fill_widths) {
+            results[*i].dimensions.width = width;
+            space -= width;
+        }
+    }
+
+    // Derive heights from the widths, now they're known
+    for (result, constraints) in results.iter_mut().zip(&items) {
+        if let Sizing::Ratio { .. } = constraints.height {
+            result.dimensions = constraints.resolve(&Dimensions::new(
+                result.dimensions.width,
+                bounds.dimensions.height,
+            ));
+        }
+    }
 
-    // Updates widths for fills, and offsets
+    // Updates offsets
     let mut offset_bounds = bounds.clone();
-    for (i, result) in results.iter_mut().enumerate() {
-        if fills.contains(&i) {
-            result.dimensions.width = fill_width;
-        }
+    for result in results.iter_mut() {
         result.origin.x = offset_bounds.origin.x;
         match arrangement.vertical() {
             Align::Start => (),
             Align::Center => {
-                result.origin.y = (offset_bounds.dimensions.height - result.dimensions.height) / 2
+                result.origin.y = offset_bounds.origin.y
+                    + (offset_bounds.dimensions.height - result.dimensions.height) / 2
             }
             Align::End => {
-                result.origin.y = offset_bounds.dimensions.height - result.dimensions.height
+                result.origin.y = offset_bounds.origin.y + offset_bounds.dimensions.height
+                    - result.dimensions.height
             }
         }
         offset_bounds.origin.x += result.dimensions.width;
@@ -102,6 +155,15 @@ pub fn solve(
     // Remove the spacer elements
     spacing.cleanup(&mut results);
 
+    // Mirror along the axis for reversed directions
+    if dir.is_reversed() {
+        let end = bounds.origin.x + bounds.dimensions.width;
+        for result in results.iter_mut() {
+            let offset = result.origin.x - bounds.origin.x;
+            result.origin.x = end.saturating_sub(offset + result.dimensions.width);
+        }
+    }
+
     // Rotate all the elements if needed
     if rotate {
         for result in results.iter_mut() {
@@ -112,83 +174,207 @@ pub fn solve(
     results
 }
 
-pub enum Spacing {
-    Even(Vec<usize>),
-    Fixed(usize, Vec<usize>),
-    None,
+/// Solves the layout like `solve_spaced`, then applies per item overrides: an
+/// alignment on the cross axis, in place of the arrangement's, and a margin
+/// which is kept clear around the item, mirrored for reversed directions.
+pub fn solve_aligned(
+    items: &[Constraints],
+    overrides: &[(Option<Align>, Margin)],
+    dir: &Dir,
+    arrangement: &Layout,
+    gaps: &Gaps,
+    bounds: &Rect,
+) -> Vec<Rect> {
+    let grown: Vec<Constraints> = items
+        .iter()
+        .zip(overrides)
+        .map(|(constraints, (_, margin))| margin.grow(constraints))
+        .collect();
+    let mut results = solve_spaced(&grown, dir, arrangement, gaps, bounds);
+
+    for (result, (align, margin)) in results.iter_mut().zip(overrides) {
+        if let Some(align) = align {
+            match dir {
+                Dir::Horizontal | Dir::HorizontalReverse => {
+                    result.origin.y = bounds.origin.y
+                        + align.offset(bounds.dimensions.height, result.dimensions.height)
+                }
+                Dir::Vertical | Dir::VerticalReverse => {
+                    result.origin.x = bounds.origin.x
+                        + align.offset(bounds.dimensions.width, result.dimensions.width)
+                }
+            }
+        }
+        *result = margin.mirror(dir).inset(result);
+    }
+    results
+}
+
+/// A fill waiting for its share of the remaining space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Share {
+    pub weight: usize,
+    pub min: usize,
+    pub max: usize,
 }
 
-impl Spacing {
-    pub fn from_arrangement(arrangement: &Layout) -> Self {
-        match arrangement {
-            Layout::Packed { v: _, h: _ } => Self::None,
-            Layout::Spaced {
-                v: _,
-                h: _,
-                spacing,
-            } => Self::Fixed(*spacing, Vec::new()),
-            Layout::Spread { v: _, h: _ } => Self::Even(Vec::new()),
+impl Share {
+    pub fn new(weight: usize, min: usize, max: usize) -> Self {
+        Self {
+            weight,
+            min,
+            max: max.max(min),
         }
     }
+}
 
-    pub fn introduce(&mut self, items: &mut Vec<Constraints>) {
-        match self {
-            Spacing::Even(indexes) => {
-                if items.len() > 1 {
-                    Self::insert_spaces(
-                        Constraints::new(Sizing::Fill, Sizing::Fill),
-                        indexes,
-                        items,
-                    );
-                }
+/// Shares space out between fills in proportion to their weights. Fills
+/// which would be given more than their max, or less than their min, are
+/// fixed at that bound and the rest of the space is shared between the
+/// others. If there isn't enough space to meet every min, the fills are
+/// truncated to fit.
+///
+/// Cells left over from rounding go one each to the fills with the largest
+/// fractional share, earlier fills first, so the whole space is always used.
+pub(crate) fn distribute(space: usize, fills: &[Share]) -> Vec<usize> {
+    let mut sizes: Vec<Option<usize>> = vec![None; fills.len()];
+    let mut space = space;
+
+    loop {
+        let active: Vec<usize> = (0..fills.len()).filter(|i| sizes[*i].is_none()).collect();
+        if active.is_empty() {
+            break;
+        }
+        let total: u128 = active.iter().map(|i| fills[*i].weight as u128).sum();
+        if total == 0 {
+            for i in active {
+                sizes[i] = Some(0);
             }
-            Spacing::Fixed(spacing, indexes) => {
-                if items.len() > 1 {
-                    Self::insert_spaces(
-                        Constraints::new(Sizing::Fixed(*spacing), Sizing::Fill),
-                        indexes,
-                        items,
-                    );
-                }
+            break;
+        }
+
+        // Compare against each fill's exact share, space * weight / total,
+        // without rounding. Widened so large weights can't overflow
+        let exact = |i: usize| space as u128 * fills[i].weight as u128;
+        let over: Vec<usize> = active
+            .iter()
+            .copied()
+            .filter(|i| (fills[*i].max as u128).saturating_mul(total) < exact(*i))
+            .collect();
+        let frozen = if !over.is_empty() {
+            over.into_iter().map(|i| (i, fills[i].max)).collect()
+        } else {
+            active
+                .iter()
+                .copied()
+                .filter(|i| (fills[*i].min as u128).saturating_mul(total) > exact(*i))
+                .map(|i| (i, fills[i].min))
+                .collect::<Vec<_>>()
+        };
+
+        if frozen.is_empty() {
+            let mut leftover = space;
+            for i in &active {
+                let size = (exact(*i) / total) as usize;
+                sizes[*i] = Some(size);
+                leftover -= size;
             }
-            Spacing::None => (),
+            let mut by_remainder = active.clone();
+            by_remainder.sort_by_key(|i| std::cmp::Reverse(exact(*i) % total));
+            for i in by_remainder.into_iter().take(leftover) {
+                sizes[i] = sizes[i].map(|s| s + 1);
+            }
+            break;
+        }
+
+        for (i, size) in frozen {
+            let size = size.min(space);
+            sizes[i] = Some(size);
+            space -= size;
         }
     }
 
-    fn insert_spaces(
-        constraints: Constraints,
-        indexes: &mut Vec<usize>,
-        items: &mut Vec<Constraints>,
-    ) {
-        let mut current = 1;
-        for _ in 0..(items.len() - 1) {
-            items.insert(current, constraints.clone());
-            indexes.push(current);
-            current += 2;
+    sizes.into_iter().map(|s| s.unwrap_or(0)).collect()
+}
+
+/// The space a layout leaves along its axis, solved for by introducing
+/// spacer items at the ends and between the items, which are removed again
+/// once the layout is solved. Spacers only share the space the items' own
+/// fills leave over.
+pub struct Spacing {
+    /// The spacer at either end, for layouts which spread space around the
+    /// items.
+    ends: Option<Sizing>,
+    /// The spacer between each pair of items.
+    between: Option<Sizing>,
+    gaps: Gaps,
+    /// Where the spacers were introduced, in the reverse order so they can
+    /// be removed one after another.
+    indexes: Vec<usize>,
+}
+
+impl Spacing {
+    pub fn new(arrangement: &Layout, gaps: &Gaps) -> Self {
+        let (ends, between) = match arrangement {
+            Layout::Packed { .. } => (None, None),
+            Layout::Spaced { spacing, .. } => (None, Some(Sizing::Fixed(*spacing))),
+            Layout::Spread { .. } => (None, Some(Sizing::Fill)),
+            Layout::SpaceAround { .. } => (Some(Sizing::Weighted(1)), Some(Sizing::Weighted(2))),
+            Layout::SpaceEvenly { .. } => (Some(Sizing::Fill), Some(Sizing::Fill)),
+        };
+        Self {
+            ends,
+            between,
+            gaps: gaps.clone(),
+            indexes: Vec::new(),
+        }
+    }
+
+    pub fn introduce(&mut self, items: &mut Vec<Constraints>) {
+        if items.is_empty() {
+            return;
+        }
+        let (leading, trailing) = self.gaps.padding();
+        let padding = |size: usize| (size > 0).then_some(Sizing::Fixed(size));
+        let mut spaced = Vec::new();
+        let mut indexes = Vec::new();
+        let mut space = |sizing: Option<Sizing>, spaced: &mut Vec<Constraints>| {
+            if let Some(sizing) = sizing {
+                indexes.push(spaced.len());
+                spaced.push(Constraints::new(sizing, Sizing::Fill));
+            }
+        };
+
+        space(padding(leading), &mut spaced);
+        space(self.ends, &mut spaced);
+        for (i, item) in items.drain(..).enumerate() {
+            if i > 0 {
+                let gap = self.gaps.gap_after(i - 1).map(Sizing::Fixed);
+                space(gap.or(self.between), &mut spaced);
+            }
+            spaced.push(item);
         }
-        // Put indexes in reverse order, since that's the order
-        // they should be removed in.
+        space(self.ends, &mut spaced);
+        space(padding(trailing), &mut spaced);
+
+        *items = spaced;
         indexes.reverse();
+        self.indexes = indexes;
+    }
+
+    pub fn is_spacer(&self, index: usize) -> bool {
+        self.indexes.contains(&index)
     }
 
     pub fn cleanup(&self, results: &mut Vec<Rect>) {
-        match self {
-            Spacing::Even(indexes) | Spacing::Fixed(_, indexes) => {
-                for i in indexes {
-                    let _ = results.remove(*i);
-                }
-            }
-            Spacing::None => (),
+        for i in &self.indexes {
+            let _ = results.remove(*i);
         }
     }
 
     #[cfg(test)]
     pub fn indexes(&self) -> Vec<usize> {
-        match self {
-            Spacing::Even(indexes) => indexes.clone(),
-            Spacing::Fixed(_, indexes) => indexes.clone(),
-            Spacing::None => Vec::new(),
-        }
+        self.indexes.clone()
     }
 }
 
@@ -301,6 +487,228 @@ mod auto_layout_tests {
         assert_eq!(results[0], Rect::new_from_raw(25, 0, 50, 50));
     }
 
+    #[test]
+    fn bounded_fill_width() {
+        let items = vec![
+            Constraints::new(Sizing::Fill.max(20), Sizing::Fill),
+            Constraints::new(Sizing::Fill, Sizing::Fill),
+        ];
+        let bounds = Rect::new_from_raw(0, 0, 100, 100);
+        let results = solve(&items, &Dir::Horizontal, &Layout::default(), &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(0, 0, 20, 100));
+        assert_eq!(results[1], Rect::new_from_raw(20, 0, 80, 100));
+
+        let items = vec![
+            Constraints::new(Sizing::Fill.min(70), Sizing::Fill),
+            Constraints::new(Sizing::Fill, Sizing::Fill.max(30)),
+        ];
+        let results = solve(&items, &Dir::Horizontal, &Layout::default(), &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(0, 0, 70, 100));
+        assert_eq!(results[1], Rect::new_from_raw(70, 0, 30, 30));
+    }
+
+    #[test]
+    fn distribute_bounds() {
+        let fill = Share::new(1, 0, usize::MAX);
+        assert_eq!(vec![30, 30, 30], distribute(90, &[fill; 3]));
+        assert_eq!(
+            vec![10, 40, 40],
+            distribute(90, &[Share::new(1, 0, 10), fill, fill])
+        );
+        assert_eq!(vec![60, 30], distribute(90, &[Share::new(1, 60, 80), fill]));
+        assert_eq!(
+            vec![30, 20],
+            distribute(50, &[Share::new(1, 60, 80), Share::new(1, 10, 20)])
+        );
+    }
+
+    #[test]
+    fn distribute_remainder() {
+        let fill = Share::new(1, 0, usize::MAX);
+        assert_eq!(vec![34, 33, 33], distribute(100, &[fill; 3]));
+        assert_eq!(
+            vec![7, 3],
+            distribute(10, &[Share::new(2, 0, usize::MAX), fill])
+        );
+        assert_eq!(vec![0, 0], distribute(10, &[Share::new(0, 0, 5); 2]));
+    }
+
+    #[test]
+    fn distribute_large_weights() {
+        let heavy = Share::new(usize::MAX, 0, usize::MAX);
+        assert_eq!(vec![50, 50], distribute(100, &[heavy; 2]));
+        assert_eq!(
+            vec![100, 0],
+            distribute(100, &[heavy, Share::new(1, 0, usize::MAX)])
+        );
+    }
+
+    #[test]
+    fn inverted_bounds() {
+        let inverted = Sizing::Bounded { min: 8, max: 4 };
+        assert_eq!(
+            vec![8, 2],
+            distribute(10, &[Share::new(1, 8, 4), Share::new(1, 0, usize::MAX)])
+        );
+        let items = vec![Constraints::new(inverted, inverted)];
+        let bounds = Rect::new_from_raw(0, 0, 20, 6);
+        let results = solve(&items, &Dir::Horizontal, &Layout::default(), &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(0, 0, 8, 6));
+    }
+
+    #[test]
+    fn weighted_and_percent_width() {
+        let items = vec![
+            Constraints::new(Sizing::Percent(25), Sizing::Fill),
+            Constraints::new(Sizing::Weighted(2), Sizing::Fill),
+            Constraints::new(Sizing::Fill, Sizing::Percent(50)),
+        ];
+        let bounds = Rect::new_from_raw(0, 0, 100, 10);
+        let results = solve(&items, &Dir::Horizontal, &Layout::default(), &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(0, 0, 25, 10));
+        assert_eq!(results[1], Rect::new_from_raw(25, 0, 50, 10));
+        assert_eq!(results[2], Rect::new_from_raw(75, 0, 25, 5));
+    }
+
+    #[test]
+    fn no_unpainted_columns() {
+        let items = vec![Constraints::new(Sizing::Fill, Sizing::Fill); 3];
+        let bounds = Rect::new_from_raw(0, 0, 80, 10);
+        let results = solve(&items, &Dir::Horizontal, &Layout::default(), &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(0, 0, 27, 10));
+        assert_eq!(results[1], Rect::new_from_raw(27, 0, 27, 10));
+        assert_eq!(results[2], Rect::new_from_raw(54, 0, 26, 10));
+    }
+
+    #[test]
+    fn cross_alignment_within_offset_bounds() {
+        let items = vec![Constraints::new(Sizing::Fixed(10), Sizing::Fixed(10))];
+        let bounds = Rect::new_from_raw(5, 20, 100, 30);
+        let arrangement = Layout::packed(Align::End, Align::Start);
+        let results = solve(&items, &Dir::Horizontal, &arrangement, &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(5, 40, 10, 10));
+
+        let arrangement = Layout::packed(Align::Center, Align::Start);
+        let results = solve(&items, &Dir::Horizontal, &arrangement, &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(5, 30, 10, 10));
+    }
+
+    #[test]
+    fn aligned_overrides_and_margins() {
+        let items = vec![
+            Constraints::new(Sizing::Fixed(10), Sizing::Fixed(10)),
+            Constraints::new(Sizing::Fixed(10), Sizing::Fixed(10)),
+            Constraints::new(Sizing::Fill, Sizing::Fill),
+        ];
+        let overrides = vec![
+            (None, Margin::default()),
+            (Some(Align::End), Margin::default()),
+            (Some(Align::Center), Margin::new(1, 2, 3, 4)),
+        ];
+        let bounds = Rect::new_from_raw(0, 10, 50, 30);
+        let results = solve_aligned(
+            &items,
+            &overrides,
+            &Dir::Horizontal,
+            &Layout::default(),
+            &Gaps::default(),
+            &bounds,
+        );
+        assert_eq!(results[0], Rect::new_from_raw(0, 10, 10, 10));
+        assert_eq!(results[1], Rect::new_from_raw(10, 30, 10, 10));
+        assert_eq!(results[2], Rect::new_from_raw(21, 13, 27, 23));
+    }
+
+    #[test]
+    fn reversed_margins() {
+        let items = vec![Constraints::new(Sizing::Fixed(10), Sizing::Fixed(10)); 2];
+        let arrangement = Layout::default();
+
+        let overrides = vec![(None, Margin::new(3, 0, 0, 0)), (None, Margin::default())];
+        let bounds = Rect::new_from_raw(0, 0, 50, 10);
+        let results = solve_aligned(
+            &items,
+            &overrides,
+            &Dir::HorizontalReverse,
+            &arrangement,
+            &Gaps::default(),
+            &bounds,
+        );
+        assert_eq!(results[0], Rect::new_from_raw(37, 0, 10, 10));
+        assert_eq!(results[1], Rect::new_from_raw(27, 0, 10, 10));
+
+        let overrides = vec![(None, Margin::new(0, 0, 3, 0)), (None, Margin::default())];
+        let bounds = Rect::new_from_raw(0, 0, 10, 50);
+        let results = solve_aligned(
+            &items,
+            &overrides,
+            &Dir::VerticalReverse,
+            &arrangement,
+            &Gaps::default(),
+            &bounds,
+        );
+        assert_eq!(results[0], Rect::new_from_raw(0, 37, 10, 10));
+        assert_eq!(results[1], Rect::new_from_raw(0, 27, 10, 10));
+    }
+
+    #[test]
+    fn ratios() {
+        let ratio = Sizing::ratio;
+        let bounds = Rect::new_from_raw(0, 0, 50, 30);
+
+        let items = vec![
+            Constraints::new(Sizing::Fill, ratio(1, 2)),
+            Constraints::new(Sizing::Fixed(10), Sizing::Fill),
+        ];
+        let results = solve(&items, &Dir::Horizontal, &Layout::default(), &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(0, 0, 40, 20));
+
+        let items = vec![
+            Constraints::new(ratio(2, 1), Sizing::Fill),
+            Constraints::new(Sizing::Fill, Sizing::Fill),
+        ];
+        let results = solve(&items, &Dir::Horizontal, &Layout::default(), &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(0, 0, 50, 25));
+        assert_eq!(results[1], Rect::new_from_raw(50, 0, 0, 30));
+    }
+
+    #[test]
+    fn ratios_exclude_margins() {
+        let bounds = Rect::new_from_raw(0, 0, 10, 20);
+        let overrides = vec![(None, Margin::all(1))];
+        for items in [
+            vec![Constraints::new(Sizing::Fill, Sizing::ratio(1, 2))],
+            vec![Constraints::new(Sizing::ratio(2, 1), Sizing::Fill)],
+        ] {
+            let results = solve_aligned(
+                &items,
+                &overrides,
+                &Dir::Horizontal,
+                &Layout::default(),
+                &Gaps::default(),
+                &bounds,
+            );
+            assert_eq!(results[0], Rect::new_from_raw(1, 1, 8, 4));
+        }
+    }
+
+    #[test]
+    fn reversed() {
+        let items = vec![
+            Constraints::new(Sizing::Fixed(10), Sizing::Fixed(10)),
+            Constraints::new(Sizing::Fixed(20), Sizing::Fixed(10)),
+        ];
+        let bounds = Rect::new_from_raw(5, 0, 50, 40);
+        let arrangement = Layout::fixed(Align::Start, Align::Start, 2);
+        let results = solve(&items, &Dir::HorizontalReverse, &arrangement, &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(45, 0, 10, 10));
+        assert_eq!(results[1], Rect::new_from_raw(23, 0, 20, 10));
+
+        let results = solve(&items, &Dir::VerticalReverse, &arrangement, &bounds);
+        assert_eq!(results[0], Rect::new_from_raw(5, 30, 10, 10));
+        assert_eq!(results[1], Rect::new_from_raw(5, 18, 20, 10));
+    }
+
     mod vertical {
         use super::*;
 
@@ -338,6 +746,19 @@ mod auto_layout_tests {
             assert_eq!(results[0], Rect::new_from_raw(0, 0, 50, 80));
             assert_eq!(results[1], Rect::new_from_raw(0, 80, 100, 120));
         }
+
+        #[test]
+        fn spaced() {
+            let items = vec![
+                Constraints::new(Sizing::Fixed(10), Sizing::Fixed(10)),
+                Constraints::new(Sizing::Fixed(20), Sizing::Fixed(10)),
+            ];
+            let bounds = Rect::new_from_raw(0, 0, 50, 40);
+            let arrangement = Layout::fixed(Align::Start, Align::Start, 2);
+            let results = solve(&items, &Dir::Vertical, &arrangement, &bounds);
+            assert_eq!(results[0], Rect::new_from_raw(0, 0, 10, 10));
+            assert_eq!(results[1], Rect::new_from_raw(0, 12, 20, 10));
+        }
     }
 }
 
@@ -365,13 +786,92 @@ mod spacing_tests {
             Constraints::new(Sizing::Fixed(10), Sizing::Fill),
         ];
 
-        let mut spacing = Spacing::from_arrangement(&Layout::Spread {
-            v: Align::Start,
-            h: Align::Start,
-        });
+        let mut spacing = Spacing::new(
+            &Layout::Spread {
+                v: Align::Start,
+                h: Align::Start,
+            },
+            &Gaps::default(),
+        );
         spacing.introduce(&mut items);
         assert_eq!(7, items.len());
         assert_eq!(vec![5, 3, 1], spacing.indexes());
         assert_eq!(expected, items);
     }
+
+    fn origins(results: &[Rect]) -> Vec<usize> {
+        results.iter().map(|r| r.origin.x).collect()
+    }
+
+    #[test]
+    fn around_and_evenly() {
+        let items = vec![Constraints::new(Sizing::Fixed(10), Sizing::Fill); 3];
+        let bounds = Rect::new_from_raw(0, 0, 60, 10);
+
+        let arrangement = Layout::around(Align::Start, Align::Start);
+        let results = solve(&items, &Dir::Horizontal, &arrangement, &bounds);
+        assert_eq!(vec![5, 25, 45], origins(&results));
+
+        let arrangement = Layout::evenly(Align::Start, Align::Start);
+        let results = solve(&items, &Dir::Horizontal, &arrangement, &bounds);
+        assert_eq!(vec![8, 26, 43], origins(&results));
+    }
+
+    #[test]
+    fn spacers_yield_to_fills() {
+        let fixed = Constraints::new(Sizing::Fixed(10), Sizing::Fill);
+        let bounds = Rect::new_from_raw(0, 0, 60, 10);
+
+        let items = vec![
+            fixed.clone(),
+            Constraints::new(Sizing::Fill, Sizing::Fill),
+            fixed.clone(),
+        ];
+        for arrangement in [
+            Layout::around(Align::Start, Align::Start),
+            Layout::evenly(Align::Start, Align::Start),
+        ] {
+            let results = solve(&items, &Dir::Horizontal, &arrangement, &bounds);
+            assert_eq!(vec![0, 10, 50], origins(&results));
+            assert_eq!(40, results[1].dimensions.width);
+        }
+
+        let items = vec![
+            fixed.clone(),
+            Constraints::new(Sizing::Bounded { min: 0, max: 20 }, Sizing::Fill),
+            fixed,
+        ];
+        let arrangement = Layout::evenly(Align::Start, Align::Start);
+        let results = solve(&items, &Dir::Horizontal, &arrangement, &bounds);
+        assert_eq!(vec![5, 20, 45], origins(&results));
+    }
+
+    #[test]
+    fn fills_take_preferred_sizes() {
+        let items = vec![
+            Constraints::new(Sizing::Fill, Sizing::Fill).prefer(Dimensions::new(10, 1)),
+            Constraints::new(Sizing::Fill, Sizing::Fill).prefer(Dimensions::new(2, 1)),
+        ];
+        let widths = |width| {
+            let bounds = Rect::new_from_raw(0, 0, width, 10);
+            solve(&items, &Dir::Horizontal, &Layout::default(), &bounds)
+                .iter()
+                .map(|r| r.dimensions.width)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![10, 2], widths(12));
+        assert_eq!(vec![15, 15], widths(30));
+        assert_eq!(vec![4, 4], widths(8));
+    }
+
+    #[test]
+    fn padding_and_gaps() {
+        let items = vec![Constraints::new(Sizing::Fixed(10), Sizing::Fill); 3];
+        let bounds = Rect::new_from_raw(0, 0, 60, 10);
+        let gaps = Gaps::new().leading(2).trailing(3).after(0, 5);
+
+        let arrangement = Layout::fixed(Align::Start, Align::End, 1);
+        let results = solve_spaced(&items, &Dir::Horizontal, &arrangement, &gaps, &bounds);
+        assert_eq!(vec![21, 36, 47], origins(&results));
+    }
 }
@@ -0,0 +1,112 @@
+use crate::values::{Constraints, Dimensions};
+use std::cell::{Cell, RefCell};
+
+/// How many measurements are kept. Containers are usually measured against
+/// their parent's bounds and then again against their own rect, so a couple
+/// of entries covers most layouts.
+const CAPACITY: usize = 4;
+
+thread_local! {
+    static FRAME: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Starts a new frame, so containers measure their children again rather
+/// than reusing what they measured for the last one.
+pub(crate) fn next_frame() {
+    FRAME.with(|frame| frame.set(frame.get().wrapping_add(1)));
+}
+
+/// Remembers the constraints a container measured for recent bounds within
+/// a frame, so measuring it again, e.g. once by its parent and again when
+/// rendering, doesn't walk all of its descendants a second time.
+#[derive(Default)]
+pub(crate) struct SizingCache {
+    frame: Cell<usize>,
+    entries: RefCell<Vec<(Dimensions, Constraints)>>,
+}
+
+impl SizingCache {
+    /// Returns the constraints measured for the bounds, measuring them with
+    /// the function if they haven't been already.
+    pub fn measure<F: FnOnce() -> Constraints>(&self, bounds: &Dimensions, f: F) -> Constraints {
+        let frame = FRAME.with(Cell::get);
+        if self.frame.replace(frame) != frame {
+            self.entries.borrow_mut().clear();
+        }
+        if let Some((_, constraints)) = self.entries.borrow().iter().find(|(b, _)| b == bounds) {
+            return constraints.clone();
+        }
+
+        let constraints = f();
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() == CAPACITY {
+            entries.remove(0);
+        }
+        entries.push((bounds.clone(), constraints.clone()));
+        constraints
+    }
+}
+
+#[cfg(test)]
+mod measure_tests {
+    use crate::buffer::Buffer;
+    use crate::layouts::Auto;
+    use crate::testing::TestTerminal;
+    use crate::values::*;
+    use crate::views::View;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Counted(Rc<Cell<usize>>);
+
+    impl View for Counted {
+        fn sizing(&self, _: &Dimensions) -> Constraints {
+            self.0.set(self.0.get() + 1);
+            Constraints::new(Sizing::Fixed(3), Sizing::Fixed(1))
+        }
+
+        fn render(&self, _: &Rect, _: &mut Buffer) {}
+    }
+
+    fn nested(depth: usize, counter: &Rc<Cell<usize>>) -> Auto {
+        let mut view = Auto::vertical()
+            .width(ContainerSizing::Hug)
+            .height(ContainerSizing::Hug)
+            .push(Counted(counter.clone()));
+        for _ in 0..depth {
+            view = Auto::horizontal()
+                .width(ContainerSizing::Hug)
+                .height(ContainerSizing::Hug)
+                .push(view);
+        }
+        view
+    }
+
+    fn leaf_measurements(depth: usize) -> usize {
+        let counter = Rc::new(Cell::new(0));
+        let view = nested(depth, &counter);
+        let dimensions = Dimensions::new(20, 5);
+        view.render(
+            &Rect::new(Point::zero(), dimensions.clone()),
+            &mut Buffer::new(dimensions),
+        );
+        counter.get()
+    }
+
+    #[test]
+    fn deep_trees_measure_leaves_a_fixed_number_of_times() {
+        assert_eq!(leaf_measurements(10), leaf_measurements(200));
+        assert!(leaf_measurements(200) <= 3);
+    }
+
+    #[test]
+    fn measures_again_each_frame() {
+        let counter = Rc::new(Cell::new(0));
+        let view = nested(10, &counter);
+        let mut terminal = TestTerminal::new(20, 5);
+        terminal.render(&view);
+        let first = counter.get();
+        terminal.render(&view);
+        assert_eq!(2 * first, counter.get());
+    }
+}
@@ -0,0 +1,488 @@
+use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
+use crate::values::*;
+use crate::views::View;
+use cassowary::strength::{MEDIUM, REQUIRED, STRONG, WEAK};
+use cassowary::WeightedRelation::{EQ, GE, LE};
+use cassowary::{Expression, Solver, Variable};
+
+/// An edge, centre line or size of an item, or of the layout itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    CenterX,
+    CenterY,
+    Width,
+    Height,
+}
+
+/// How hard the solver tries to satisfy a relation. Required relations
+/// always hold, unless they contradict each other, in which case the later
+/// one is dropped. The others give way to stronger ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strength {
+    Required,
+    Strong,
+    Medium,
+    Weak,
+}
+
+impl Strength {
+    fn weight(&self) -> f64 {
+        match self {
+            Strength::Required => REQUIRED,
+            Strength::Strong => STRONG,
+            Strength::Medium => MEDIUM,
+            Strength::Weak => WEAK,
+        }
+    }
+}
+
+/// An edge of a named item, or of the parent when there's no name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anchor {
+    item: Option<String>,
+    edge: Edge,
+}
+
+impl Anchor {
+    pub fn of(item: &str, edge: Edge) -> Self {
+        Self {
+            item: Some(item.to_string()),
+            edge,
+        }
+    }
+
+    /// An edge of the layout itself. Its left and top edges are always 0.
+    pub fn parent(edge: Edge) -> Self {
+        Self { item: None, edge }
+    }
+
+    pub fn equals<T: Into<Target>>(self, target: T) -> Relation {
+        Relation::new(self, Op::Equal, target.into())
+    }
+
+    pub fn at_least<T: Into<Target>>(self, target: T) -> Relation {
+        Relation::new(self, Op::AtLeast, target.into())
+    }
+
+    pub fn at_most<T: Into<Target>>(self, target: T) -> Relation {
+        Relation::new(self, Op::AtMost, target.into())
+    }
+
+    pub fn times(self, multiplier: f64) -> Target {
+        Target::from(self).times(multiplier)
+    }
+
+    pub fn percent(self, percent: usize) -> Target {
+        Target::from(self).percent(percent)
+    }
+
+    pub fn plus(self, offset: isize) -> Target {
+        Target::from(self).plus(offset)
+    }
+
+    /// The anchor in terms of the solver's variables, or `None` if it names
+    /// an item which isn't in the layout.
+    fn expression(&self, items: &[Placed], parent: &Dimensions) -> Option<Expression> {
+        let name = match &self.item {
+            Some(name) => name,
+            None => {
+                let value = match self.edge {
+                    Edge::Left | Edge::Top => 0.0,
+                    Edge::Right | Edge::Width => parent.width as f64,
+                    Edge::Bottom | Edge::Height => parent.height as f64,
+                    Edge::CenterX => parent.width as f64 / 2.0,
+                    Edge::CenterY => parent.height as f64 / 2.0,
+                };
+                return Some(Expression::from_constant(value));
+            }
+        };
+        let item = items.iter().find(|item| &item.name == name)?;
+        Some(match self.edge {
+            Edge::Left => item.left.into(),
+            Edge::Top => item.top.into(),
+            Edge::Width => item.width.into(),
+            Edge::Height => item.height.into(),
+            Edge::Right => item.left + item.width,
+            Edge::Bottom => item.top + item.height,
+            Edge::CenterX => item.left + item.width * 0.5,
+            Edge::CenterY => item.top + item.height * 0.5,
+        })
+    }
+}
+
+/// The right hand side of a relation: an anchor scaled and offset, or just
+/// a fixed value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Target {
+    anchor: Option<Anchor>,
+    multiplier: f64,
+    offset: f64,
+}
+
+impl Target {
+    pub fn times(mut self, multiplier: f64) -> Self {
+        self.multiplier *= multiplier;
+        self
+    }
+
+    pub fn percent(self, percent: usize) -> Self {
+        self.times(percent as f64 / 100.0)
+    }
+
+    pub fn plus(mut self, offset: isize) -> Self {
+        self.offset += offset as f64;
+        self
+    }
+
+    fn expression(&self, items: &[Placed], parent: &Dimensions) -> Option<Expression> {
+        let anchor = match &self.anchor {
+            Some(anchor) => anchor.expression(items, parent)?,
+            None => Expression::from_constant(0.0),
+        };
+        Some(anchor * self.multiplier + self.offset)
+    }
+}
+
+impl From<Anchor> for Target {
+    fn from(anchor: Anchor) -> Self {
+        Self {
+            anchor: Some(anchor),
+            multiplier: 1.0,
+            offset: 0.0,
+        }
+    }
+}
+
+impl From<usize> for Target {
+    fn from(value: usize) -> Self {
+        Self {
+            anchor: None,
+            multiplier: 1.0,
+            offset: value as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    AtLeast,
+    AtMost,
+}
+
+/// A linear relation between an anchor and a target, e.g. the left edge of
+/// one item equals the right edge of another plus 2. Relations are required
+/// unless given another strength.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relation {
+    anchor: Anchor,
+    op: Op,
+    target: Target,
+    strength: Strength,
+}
+
+impl Relation {
+    fn new(anchor: Anchor, op: Op, target: Target) -> Self {
+        Self {
+            anchor,
+            op,
+            target,
+            strength: Strength::Required,
+        }
+    }
+
+    pub fn strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+struct ConstraintItem {
+    name: String,
+    item: Box<dyn View>,
+}
+
+/// The solver's variables for an item.
+struct Placed {
+    name: String,
+    left: Variable,
+    top: Variable,
+    width: Variable,
+    height: Variable,
+}
+
+/// A layout which places named items by solving the relations declared
+/// between their edges, and those of the layout, rather than flowing them
+/// in a direction.
+///
+/// Items are kept within the layout and prefer the size from their own
+/// sizing, but both give way to strong and required relations. An
+/// item which isn't related to anything sits in the top left corner.
+/// Relations naming items which haven't been added are ignored.
+///
+/// When hugging, the relations are solved within the bounds offered and the
+/// layout is sized to reach the far edges of its items.
+pub struct ConstraintLayout {
+    width: ContainerSizing,
+    height: ContainerSizing,
+    items: Vec<ConstraintItem>,
+    relations: Vec<Relation>,
+}
+
+impl ConstraintLayout {
+    pub fn new() -> Self {
+        Self {
+            width: ContainerSizing::Fill,
+            height: ContainerSizing::Fill,
+            items: Vec::new(),
+            relations: Vec::new(),
+        }
+    }
+
+    pub fn width(mut self, width: ContainerSizing) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: ContainerSizing) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Adds an item, named so relations can refer to it. Items are rendered
+    /// in the order they're added.
+    pub fn push<V: View>(mut self, name: &str, item: V) -> Self {
+        self.items.push(ConstraintItem {
+            name: name.to_string(),
+            item: Box::new(item),
+        });
+        self
+    }
+
+    pub fn relate(mut self, relation: Relation) -> Self {
+        self.relations.push(relation);
+        self
+    }
+
+    /// Solves the relations for the rect of each item.
+    fn arrange(&self, within: &Rect) -> Vec<(&ConstraintItem, Rect)> {
+        let parent = &within.dimensions;
+        let mut solver = Solver::new();
+        let placed: Vec<Placed> = self
+            .items
+            .iter()
+            .map(|item| Placed {
+                name: item.name.clone(),
+                left: Variable::new(),
+                top: Variable::new(),
+                width: Variable::new(),
+                height: Variable::new(),
+            })
+            .collect();
+
+        // Adding a constraint only fails when a required one can't be
+        // satisfied, or is duplicated, in which case it's dropped.
+        for (item, vars) in self.items.iter().zip(&placed) {
+            let constraints = item.item.sizing(parent);
+            let axes = [
+                (vars.left, vars.width, constraints.width, parent.width),
+                (vars.top, vars.height, constraints.height, parent.height),
+            ];
+            for (start, size, sizing, available) in axes {
+                let preferred = sizing.resolve(available) as f64;
+                let available = available as f64;
+                let _ = solver.add_constraints(&[
+                    size | GE(REQUIRED) | 0.0,
+                    start | GE(MEDIUM) | 0.0,
+                    (start + size) | LE(MEDIUM) | available,
+                    start | EQ(WEAK) | 0.0,
+                    size | GE(MEDIUM) | sizing.minimum() as f64,
+                    size | EQ(WEAK) | preferred,
+                ]);
+                match sizing {
+                    Sizing::Fixed(n) => {
+                        let _ = solver.add_constraint(size | LE(MEDIUM) | n as f64);
+                    }
+                    Sizing::Bounded { max, .. } if (max as f64) < available => {
+                        let _ = solver.add_constraint(size | LE(MEDIUM) | max as f64);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for relation in &self.relations {
+            let anchor = relation.anchor.expression(&placed, parent);
+            let target = relation.target.expression(&placed, parent);
+            if let (Some(anchor), Some(target)) = (anchor, target) {
+                let strength = relation.strength.weight();
+                let op = match relation.op {
+                    Op::Equal => EQ(strength),
+                    Op::AtLeast => GE(strength),
+                    Op::AtMost => LE(strength),
+                };
+                let _ = solver.add_constraint(anchor | op | target);
+            }
+        }
+
+        // Edges are rounded rather than sizes, so items related edge to edge
+        // don't gain a gap or overlap.
+        let edge = |value: f64, limit: usize| (value.round().max(0.0) as usize).min(limit);
+        self.items
+            .iter()
+            .zip(&placed)
+            .map(|(item, vars)| {
+                let left = solver.get_value(vars.left);
+                let top = solver.get_value(vars.top);
+                let x = edge(left, parent.width);
+                let y = edge(top, parent.height);
+                let right = edge(left + solver.get_value(vars.width), parent.width).max(x);
+                let bottom = edge(top + solver.get_value(vars.height), parent.height).max(y);
+                let rect = Rect::new(
+                    Point::new(within.origin.x + x, within.origin.y + y),
+                    Dimensions::new(right - x, bottom - y),
+                );
+                (item, rect)
+            })
+            .collect()
+    }
+
+    /// Solves the relations within the bounds to work out how large the
+    /// layout is when hugging its items.
+    fn measure(&self, bounds: &Dimensions) -> Constraints {
+        let (mut width, mut height) = (0, 0);
+        if self.width.hugs() || self.height.hugs() {
+            for (_, rect) in self.arrange(&Rect::new(Point::zero(), bounds.clone())) {
+                width = width.max(rect.origin.x + rect.dimensions.width);
+                height = height.max(rect.origin.y + rect.dimensions.height);
+            }
+        }
+        Constraints::around(&self.width, &self.height, Dimensions::new(width, height))
+    }
+}
+
+impl Default for ConstraintLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for ConstraintLayout {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.measure(bounds)
+    }
+
+    fn render(&self, within: &Rect, buffer: &mut Buffer) {
+        for (item, rect) in self.arrange(within) {
+            item.item.render(&rect, buffer);
+        }
+    }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        let children = self
+            .arrange(within)
+            .into_iter()
+            .map(|(item, rect)| item.item.inspect(&rect))
+            .collect();
+        LayoutNode::of(self, within).children(children)
+    }
+}
+
+#[cfg(test)]
+mod constraint_tests {
+    use super::*;
+    use crate::testing::TestTerminal;
+    use crate::views::Label;
+
+    fn rects(layout: &ConstraintLayout, width: usize, height: usize) -> Vec<Rect> {
+        layout
+            .inspect(&Rect::new(Point::zero(), Dimensions::new(width, height)))
+            .children
+            .into_iter()
+            .map(|child| child.rect)
+            .collect()
+    }
+
+    #[test]
+    fn relates_edges() {
+        TestTerminal::new(12, 2)
+            .render(
+                &ConstraintLayout::new()
+                    .push("a", Label::new("one"))
+                    .push("b", Label::new("two"))
+                    .relate(
+                        Anchor::of("b", Edge::Left).equals(Anchor::of("a", Edge::Right).plus(2)),
+                    )
+                    .relate(Anchor::of("b", Edge::Bottom).equals(Anchor::parent(Edge::Bottom))),
+            )
+            .assert_text(
+                "
+one
+     two",
+            );
+    }
+
+    #[test]
+    fn proportional_sizes() {
+        let layout = ConstraintLayout::new()
+            .push("side", Label::new("x").width(ContainerSizing::Fill))
+            .push("main", Label::new("y").width(ContainerSizing::Fill))
+            .relate(
+                Anchor::of("side", Edge::Width).at_least(Anchor::parent(Edge::Width).percent(30)),
+            )
+            .relate(
+                Anchor::of("side", Edge::Width)
+                    .equals(8)
+                    .strength(Strength::Strong),
+            )
+            .relate(Anchor::of("main", Edge::Left).equals(Anchor::of("side", Edge::Right)))
+            .relate(Anchor::of("main", Edge::Right).equals(Anchor::parent(Edge::Right)));
+
+        assert_eq!(
+            vec![
+                Rect::new_from_raw(0, 0, 8, 1),
+                Rect::new_from_raw(8, 0, 12, 1)
+            ],
+            rects(&layout, 20, 1)
+        );
+        assert_eq!(
+            vec![
+                Rect::new_from_raw(0, 0, 12, 1),
+                Rect::new_from_raw(12, 0, 28, 1)
+            ],
+            rects(&layout, 40, 1)
+        );
+    }
+
+    #[test]
+    fn drops_contradictions() {
+        let layout = ConstraintLayout::new()
+            .push("a", Label::new("a"))
+            .relate(Anchor::of("a", Edge::Left).equals(3))
+            .relate(Anchor::of("a", Edge::Left).equals(5))
+            .relate(Anchor::of("missing", Edge::Left).equals(1));
+
+        assert_eq!(vec![Rect::new_from_raw(3, 0, 1, 1)], rects(&layout, 10, 1));
+    }
+
+    #[test]
+    fn hugs_items() {
+        let layout = ConstraintLayout::new()
+            .width(ContainerSizing::Hug)
+            .height(ContainerSizing::Hug)
+            .push("a", Label::new("one"))
+            .push("b", Label::new("two"))
+            .relate(Anchor::of("b", Edge::Left).equals(Anchor::of("a", Edge::Right).plus(1)))
+            .relate(Anchor::of("b", Edge::Top).equals(Anchor::of("a", Edge::Bottom)));
+
+        assert_eq!(
+            Constraints::new(Sizing::Fixed(7), Sizing::Fixed(2)),
+            layout.sizing(&Dimensions::new(20, 10))
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
+use crate::layouts::auto_solver::solve;
+use crate::layouts::cache::SizingCache;
+use crate::values::*;
+use crate::views::View;
+use std::ops::Range;
+
+/// A layout which places its children one after another along its axis,
+/// starting a new line when the next child doesn't fit, e.g. for tag lists
+/// or toolbars which should reflow on narrow terminals.
+///
+/// The layout's alignment and spacing are applied to each line separately.
+/// With `Layout::Spaced`, the spacing is also put between lines. Children
+/// which fill the axis take up a line of their own.
+pub struct Flow {
+    dir: Dir,
+    layout: Layout,
+    width: ContainerSizing,
+    height: ContainerSizing,
+    items: Vec<Box<dyn View>>,
+    cache: SizingCache,
+}
+
+/// A line of children, measured along the flow's axis.
+struct FlowLine {
+    items: Range<usize>,
+    length: usize,
+    thickness: usize,
+}
+
+impl Flow {
+    pub fn new(dir: Dir, layout: Layout, width: ContainerSizing, height: ContainerSizing) -> Self {
+        Flow {
+            dir,
+            layout,
+            width,
+            height,
+            items: Vec::new(),
+            cache: SizingCache::default(),
+        }
+    }
+
+    pub fn horizontal() -> Self {
+        Self::new(
+            Dir::Horizontal,
+            Layout::default(),
+            ContainerSizing::Fill,
+            ContainerSizing::Fill,
+        )
+    }
+
+    pub fn vertical() -> Self {
+        Self::new(
+            Dir::Vertical,
+            Layout::default(),
+            ContainerSizing::Fill,
+            ContainerSizing::Fill,
+        )
+    }
+
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Places items from the right, or the bottom, instead. Lines still
+    /// wrap downwards, or rightwards.
+    pub fn reverse(mut self) -> Self {
+        self.dir = self.dir.reverse();
+        self
+    }
+
+    pub fn width(mut self, width: ContainerSizing) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: ContainerSizing) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn push<V: View>(mut self, item: V) -> Self {
+        self.items.push(Box::new(item));
+        self
+    }
+
+    pub fn push_each<I, F, V>(mut self, items: I, render: F) -> Self
+    where
+        I: IntoIterator,
+        V: View,
+        F: Fn(&I::Item) -> V,
+    {
+        for item in items {
+            self.items.push(Box::new(render(&item)));
+        }
+        self
+    }
+
+    pub fn maybe_add<V: View>(mut self, check: bool, item: V) -> Self {
+        if check {
+            self.items.push(Box::new(item));
+        }
+        self
+    }
+
+    fn spacing(&self) -> usize {
+        match self.layout {
+            Layout::Spaced { spacing, .. } => spacing,
+            _ => 0,
+        }
+    }
+
+    /// Sizes the children and breaks them into lines. Everything is rotated
+    /// for vertical flows, so lines always run along the width.
+    fn lines(&self, bounds: &Dimensions) -> (Vec<Constraints>, Vec<FlowLine>) {
+        let rotate = self.dir.is_vertical();
+        let mut rotated = bounds.clone();
+        if rotate {
+            rotated.rotate();
+        }
+        let spacing = self.spacing();
+
+        let mut items = Vec::new();
+        let mut lines = Vec::new();
+        let mut line = FlowLine {
+            items: 0..0,
+            length: 0,
+            thickness: 0,
+        };
+
+        for (i, item) in self.items.iter().enumerate() {
+            let mut constraints = item.sizing(bounds);
+            if rotate {
+                constraints.rotate();
+            }
+            let length = match constraints.width.weight() {
+                Some(_) => rotated.width,
+                None => constraints.width.resolve(rotated.width),
+            };
+
+            let gap = if line.items.is_empty() { 0 } else { spacing };
+            if !line.items.is_empty() && line.length + gap + length > rotated.width {
+                let next = FlowLine {
+                    items: i..i,
+                    length: 0,
+                    thickness: 0,
+                };
+                lines.push(std::mem::replace(&mut line, next));
+            }
+
+            let gap = if line.items.is_empty() { 0 } else { spacing };
+            line.items.end = i + 1;
+            line.length += gap + length;
+            line.thickness = line.thickness.max(constraints.preferred.height);
+            items.push(constraints);
+        }
+
+        if !line.items.is_empty() {
+            lines.push(line);
+        }
+        (items, lines)
+    }
+
+    /// Breaks the children into lines to work out how large the layout is
+    /// when hugging them.
+    fn measure(&self, bounds: &Dimensions) -> Constraints {
+        let (_, lines) = self.lines(bounds);
+        let length = lines.iter().map(|l| l.length).max().unwrap_or(0);
+        let thickness = lines.iter().map(|l| l.thickness).sum::<usize>()
+            + self.spacing() * lines.len().saturating_sub(1);
+
+        let (width, height) = match self.dir {
+            Dir::Horizontal | Dir::HorizontalReverse => (length, thickness),
+            Dir::Vertical | Dir::VerticalReverse => (thickness, length),
+        };
+        let content = Dimensions::new(width.clamp(0, bounds.width), height.clamp(0, bounds.height));
+        Constraints::around(&self.width, &self.height, content)
+    }
+}
+
+impl View for Flow {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.cache.measure(bounds, || self.measure(bounds))
+    }
+
+    fn render(&self, within: &Rect, buffer: &mut Buffer) {
+        for (rect, item) in self.arrange(within) {
+            item.render(&rect, buffer);
+        }
+    }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        let children = self
+            .arrange(within)
+            .into_iter()
+            .map(|(rect, item)| item.inspect(&rect))
+            .collect();
+        LayoutNode::of(self, within)
+            .children(children)
+            .check_hug(self, &self.width, &self.height)
+    }
+}
+
+impl Flow {
+    /// The rect for each item on a line which fits within the rect.
+    fn arrange(&self, within: &Rect) -> Vec<(Rect, &dyn View)> {
+        let rotate = self.dir.is_vertical();
+        let (items, lines) = self.lines(&within.dimensions);
+
+        let mut bounds = within.clone();
+        let mut layout = self.layout.clone();
+        if rotate {
+            bounds.rotate();
+            layout.rotate();
+        }
+
+        // Lines run along the width once rotated, mirrored when reversed
+        let along = if self.dir.is_reversed() {
+            Dir::HorizontalReverse
+        } else {
+            Dir::Horizontal
+        };
+
+        let mut arranged = Vec::new();
+        let mut offset = bounds.origin.y;
+        for line in lines {
+            if offset >= bounds.origin.y + bounds.dimensions.height {
+                break;
+            }
+            let line_bounds = Rect::new_from_raw(
+                bounds.origin.x,
+                offset,
+                bounds.dimensions.width,
+                line.thickness
+                    .min(bounds.origin.y + bounds.dimensions.height - offset),
+            );
+            let results = solve(&items[line.items.clone()], &along, &layout, &line_bounds);
+            for (mut rect, item) in results.into_iter().zip(&self.items[line.items]) {
+                if rotate {
+                    rect.rotate();
+                }
+                arranged.push((rect, item.as_ref()));
+            }
+            offset += line.thickness + self.spacing();
+        }
+        arranged
+    }
+}
+
+#[cfg(test)]
+mod flow_tests {
+    use super::*;
+    use crate::testing::TestTerminal;
+    use crate::views::{Label, Spacer};
+
+    fn tags() -> Flow {
+        Flow::horizontal()
+            .layout(Layout::fixed(Align::Start, Align::Start, 1))
+            .push_each(["rust", "tui", "layout", "terminal"], |t| Label::new(*t))
+    }
+
+    #[test]
+    fn wraps_onto_new_lines() {
+        TestTerminal::new(12, 5).render(&tags()).assert_text(
+            "
+rust tui
+
+layout
+
+terminal",
+        );
+    }
+
+    #[test]
+    fn aligns_each_line() {
+        TestTerminal::new(9, 2)
+            .render(
+                &Flow::horizontal()
+                    .layout(Layout::packed(Align::Start, Align::End))
+                    .push_each(["abcd", "efgh", "ij"], |t| Label::new(*t)),
+            )
+            .assert_text(
+                "
+ abcdefgh
+       ij",
+            );
+    }
+
+    #[test]
+    fn right_to_left() {
+        TestTerminal::new(12, 3)
+            .render(
+                &Flow::new(
+                    Dir::inline(TextDirection::RightToLeft),
+                    Layout::fixed(Align::Start, Align::Start, 1),
+                    ContainerSizing::Fill,
+                    ContainerSizing::Fill,
+                )
+                .push_each(["rust", "tui", "layout"], |t| Label::new(*t)),
+            )
+            .assert_text(
+                "
+    tui rust
+
+      layout",
+            );
+    }
+
+    #[test]
+    fn vertical_columns() {
+        TestTerminal::new(6, 2)
+            .render(&Flow::vertical().push_each(["a", "b", "c"], |t| Label::new(*t)))
+            .assert_text(
+                "
+ac
+b",
+            );
+    }
+
+    #[test]
+    fn hugs_wrapped_lines() {
+        let flow = tags()
+            .width(ContainerSizing::Hug)
+            .height(ContainerSizing::Hug);
+        assert_eq!(
+            Constraints::new(Sizing::Fixed(8), Sizing::Fixed(5)),
+            flow.sizing(&Dimensions::new(12, 10))
+        );
+    }
+
+    #[test]
+    fn empty_lines_take_no_space() {
+        let flow = Flow::horizontal()
+            .height(ContainerSizing::Hug)
+            .push(Label::new("a"))
+            .push(Spacer::new())
+            .push(Label::new("b"));
+        assert_eq!(
+            Sizing::Fixed(2),
+            flow.sizing(&Dimensions::new(12, 10)).height
+        );
+    }
+}
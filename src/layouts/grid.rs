@@ -0,0 +1,230 @@
+use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
+use crate::layouts::auto_solver::solve;
+use crate::layouts::cache::SizingCache;
+use crate::values::*;
+use crate::views::View;
+
+/// A layout which places its children into cells of shared column and row
+/// tracks, so content lines up across rows even when widths differ.
+///
+/// Each track is sized like a container: `Fixed`, `Fill` (and its weighted,
+/// percentage and bounded variants), or `Hug`, which is as large as the
+/// largest item in it. Items spanning several tracks don't count towards
+/// hugging.
+pub struct Grid {
+    columns: Vec<ContainerSizing>,
+    rows: Vec<ContainerSizing>,
+    width: ContainerSizing,
+    height: ContainerSizing,
+    cells: Vec<GridCell>,
+    cache: SizingCache,
+}
+
+struct GridCell {
+    column: usize,
+    row: usize,
+    columns: usize,
+    rows: usize,
+    item: Box<dyn View>,
+}
+
+impl Grid {
+    pub fn new(columns: Vec<ContainerSizing>, rows: Vec<ContainerSizing>) -> Self {
+        Self {
+            columns,
+            rows,
+            width: ContainerSizing::Fill,
+            height: ContainerSizing::Fill,
+            cells: Vec::new(),
+            cache: SizingCache::default(),
+        }
+    }
+
+    pub fn width(mut self, width: ContainerSizing) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: ContainerSizing) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Places an item in a single cell.
+    pub fn push<V: View>(self, column: usize, row: usize, item: V) -> Self {
+        self.span(column, row, 1, 1, item)
+    }
+
+    /// Places an item across several columns and rows, starting from the
+    /// top left cell. Cells outside the grid's tracks aren't rendered.
+    pub fn span<V: View>(
+        mut self,
+        column: usize,
+        row: usize,
+        columns: usize,
+        rows: usize,
+        item: V,
+    ) -> Self {
+        self.cells.push(GridCell {
+            column,
+            row,
+            columns: columns.max(1),
+            rows: rows.max(1),
+            item: Box::new(item),
+        });
+        self
+    }
+
+    /// Resolves the tracks of one axis to a concrete size each, hugging
+    /// their contents where needed.
+    fn track_sizing(&self, dir: &Dir, bounds: &Dimensions) -> Vec<Sizing> {
+        let tracks = match dir {
+            Dir::Horizontal | Dir::HorizontalReverse => &self.columns,
+            Dir::Vertical | Dir::VerticalReverse => &self.rows,
+        };
+        let mut hugs = vec![0; tracks.len()];
+        for cell in &self.cells {
+            let (track, span) = match dir {
+                Dir::Horizontal | Dir::HorizontalReverse => (cell.column, cell.columns),
+                Dir::Vertical | Dir::VerticalReverse => (cell.row, cell.rows),
+            };
+            if span != 1 || track >= tracks.len() {
+                continue;
+            }
+            if let ContainerSizing::Hug | ContainerSizing::HugBounded { .. } = tracks[track] {
+                let constraints = cell.item.sizing(bounds);
+                let size = match dir {
+                    Dir::Horizontal | Dir::HorizontalReverse => constraints.preferred.width,
+                    Dir::Vertical | Dir::VerticalReverse => constraints.preferred.height,
+                };
+                hugs[track] = hugs[track].max(size);
+            }
+        }
+        tracks
+            .iter()
+            .zip(hugs)
+            .map(|(track, hug)| track.simplify(hug))
+            .collect()
+    }
+
+    /// The position and size of each track along one axis.
+    fn tracks(&self, dir: &Dir, within: &Rect) -> Vec<Rect> {
+        let items: Vec<Constraints> = self
+            .track_sizing(dir, &within.dimensions)
+            .into_iter()
+            .map(|sizing| match dir {
+                Dir::Horizontal | Dir::HorizontalReverse => Constraints::new(sizing, Sizing::Fill),
+                Dir::Vertical | Dir::VerticalReverse => Constraints::new(Sizing::Fill, sizing),
+            })
+            .collect();
+        solve(&items, dir, &Layout::default(), within)
+    }
+
+    /// The rect for each cell within the grid's tracks.
+    fn arrange(&self, within: &Rect) -> Vec<(&GridCell, Rect)> {
+        let columns = self.tracks(&Dir::Horizontal, within);
+        let rows = self.tracks(&Dir::Vertical, within);
+
+        let mut cells = Vec::new();
+        for cell in &self.cells {
+            if cell.column >= columns.len() || cell.row >= rows.len() {
+                continue;
+            }
+            let columns = &columns[cell.column..(cell.column + cell.columns).min(columns.len())];
+            let rows = &rows[cell.row..(cell.row + cell.rows).min(rows.len())];
+
+            let rect = Rect::new(
+                Point::new(columns[0].origin.x, rows[0].origin.y),
+                Dimensions::new(
+                    columns.iter().map(|c| c.dimensions.width).sum(),
+                    rows.iter().map(|r| r.dimensions.height).sum(),
+                ),
+            );
+            cells.push((cell, rect));
+        }
+        cells
+    }
+}
+
+impl View for Grid {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.cache.measure(bounds, || {
+            let total = |dir: &Dir, limit: usize| {
+                self.track_sizing(dir, bounds)
+                    .iter()
+                    .map(Sizing::minimum)
+                    .sum::<usize>()
+                    .clamp(0, limit)
+            };
+            let content = Dimensions::new(
+                total(&Dir::Horizontal, bounds.width),
+                total(&Dir::Vertical, bounds.height),
+            );
+            Constraints::around(&self.width, &self.height, content)
+        })
+    }
+
+    fn render(&self, within: &Rect, buffer: &mut Buffer) {
+        for (cell, rect) in self.arrange(within) {
+            cell.item.render(&rect, buffer);
+        }
+    }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        let children = self
+            .arrange(within)
+            .into_iter()
+            .map(|(cell, rect)| cell.item.inspect(&rect))
+            .collect();
+        LayoutNode::of(self, within)
+            .children(children)
+            .check_hug(self, &self.width, &self.height)
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use super::*;
+    use crate::testing::TestTerminal;
+    use crate::views::Label;
+
+    #[test]
+    fn hugged_columns_line_up() {
+        TestTerminal::new(12, 3)
+            .render(
+                &Grid::new(
+                    vec![ContainerSizing::Hug, ContainerSizing::Fill],
+                    vec![ContainerSizing::Fixed(1); 3],
+                )
+                .push(0, 0, Label::new("a"))
+                .push(1, 0, Label::new("first"))
+                .push(0, 1, Label::new("bbb"))
+                .push(1, 1, Label::new("second"))
+                .span(0, 2, 2, 1, Label::new("spanning both")),
+            )
+            .assert_text(
+                "
+a  first
+bbbsecond
+spanning bot",
+            );
+    }
+
+    #[test]
+    fn sizing_hugs_tracks() {
+        let grid = Grid::new(
+            vec![ContainerSizing::Fixed(4), ContainerSizing::Hug],
+            vec![ContainerSizing::Hug, ContainerSizing::Fill],
+        )
+        .width(ContainerSizing::Hug)
+        .height(ContainerSizing::Hug)
+        .push(1, 0, Label::new("abc"))
+        .span(0, 1, 2, 1, Label::new("too wide to count"));
+
+        assert_eq!(
+            Constraints::new(Sizing::Fixed(7), Sizing::Fixed(1)),
+            grid.sizing(&Dimensions::new(20, 20))
+        );
+    }
+}
@@ -1,8 +1,21 @@
 mod auto;
 mod auto_solver;
+pub(crate) mod cache;
+mod constraint;
+mod flow;
+mod grid;
 mod pin;
+mod responsive;
 mod scroll_box;
+mod split;
+mod stack;
 
-pub use auto::Auto;
+pub use auto::{Aligned, Auto};
+pub use constraint::{Anchor, ConstraintLayout, Edge, Relation, Strength, Target};
+pub use flow::Flow;
+pub use grid::Grid;
 pub use pin::{Pin, PinBoard, PinOrigin};
+pub use responsive::{Breakpoint, Responsive};
 pub use scroll_box::ScrollBox;
+pub use split::{Split, SplitState};
+pub use stack::Stack;
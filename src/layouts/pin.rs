@@ -1,4 +1,5 @@
 use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
 use crate::values::{Constraints, Dimensions, Point, Rect, Sizing};
 use crate::views::View;
 
@@ -17,7 +18,7 @@ impl PinBoard {
         }
     }
 
-    pub fn add<V: View>(mut self, origin: PinOrigin, item: V) -> Self {
+    pub fn push<V: View>(mut self, origin: PinOrigin, item: V) -> Self {
         self.pins.push(Pin::new(origin, item));
         self
     }
@@ -32,79 +33,23 @@ impl PinBoard {
 
 impl View for PinBoard {
     fn sizing(&self, _: &Dimensions) -> Constraints {
-        Constraints {
-            width: self.width,
-            height: self.height,
-        }
+        Constraints::new(self.width, self.height)
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
         for pin in &self.pins {
-            match &pin.origin {
-                PinOrigin::TopLeft(point)
-                | PinOrigin::TopRight(point)
-                | PinOrigin::BottomLeft(point)
-                | PinOrigin::BottomRight(point) => {
-                    let mut dimensions = Dimensions::new(
-                        within.dimensions.width - point.x,
-                        within.dimensions.height - point.y,
-                    );
-                    let constraints = pin.item.sizing(&dimensions);
-                    if let Sizing::Fixed(size) = constraints.width {
-                        dimensions.width = size.clamp(0, dimensions.width)
-                    }
-                    if let Sizing::Fixed(size) = constraints.height {
-                        dimensions.height = size.clamp(0, dimensions.height)
-                    }
-
-                    let offset = match &pin.origin {
-                        PinOrigin::TopLeft(_) => {
-                            Point::new(point.x + within.origin.x, point.y + within.origin.y)
-                        }
-                        PinOrigin::TopRight(_) => Point::new(
-                            (within.dimensions.width - dimensions.width - point.x)
-                                + within.origin.x,
-                            within.origin.y + point.y,
-                        ),
-                        PinOrigin::BottomLeft(_) => Point::new(
-                            point.x + within.origin.x,
-                            (within.dimensions.height - dimensions.height - point.y)
-                                + within.origin.y,
-                        ),
-                        PinOrigin::BottomRight(_) => Point::new(
-                            (within.dimensions.width - dimensions.width - point.x)
-                                + within.origin.x,
-                            (within.dimensions.height - dimensions.height - point.y)
-                                + within.origin.y,
-                        ),
-                        _ => within.origin.clone(),
-                    };
-
-                    let rect = Rect::new(offset, dimensions);
-                    pin.item.render(&rect, buffer);
-                }
-                PinOrigin::Center => {
-                    let constraints = pin.item.sizing(&within.dimensions);
-                    let dimensions = Dimensions::new(
-                        match constraints.width {
-                            Sizing::Fill => within.dimensions.width,
-                            Sizing::Fixed(size) => size.clamp(0, within.dimensions.width),
-                        },
-                        match constraints.height {
-                            Sizing::Fill => within.dimensions.height,
-                            Sizing::Fixed(size) => size.clamp(0, within.dimensions.height),
-                        },
-                    );
-                    let point = Point::new(
-                        (within.dimensions.width - dimensions.width) / 2 + within.origin.x,
-                        (within.dimensions.height - dimensions.height) / 2 + within.origin.y,
-                    );
-                    let rect = Rect::new(point, dimensions);
-                    pin.item.render(&rect, buffer);
-                }
-            }
+            pin.item.render(&pin.place(within), buffer);
         }
     }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        let children = self
+            .pins
+            .iter()
+            .map(|pin| pin.item.inspect(&pin.place(within)))
+            .collect();
+        LayoutNode::of(self, within).children(children)
+    }
 }
 
 pub enum PinOrigin {
@@ -127,4 +72,52 @@ impl Pin {
             item: Box::new(item),
         }
     }
+
+    /// The rect the item is rendered in, within the board.
+    fn place(&self, within: &Rect) -> Rect {
+        match &self.origin {
+            PinOrigin::TopLeft(point)
+            | PinOrigin::TopRight(point)
+            | PinOrigin::BottomLeft(point)
+            | PinOrigin::BottomRight(point) => {
+                let available = Dimensions::new(
+                    within.dimensions.width - point.x,
+                    within.dimensions.height - point.y,
+                );
+                let dimensions = self.item.sizing(&available).resolve(&available);
+
+                let offset = match &self.origin {
+                    PinOrigin::TopLeft(_) => {
+                        Point::new(point.x + within.origin.x, point.y + within.origin.y)
+                    }
+                    PinOrigin::TopRight(_) => Point::new(
+                        (within.dimensions.width - dimensions.width - point.x) + within.origin.x,
+                        within.origin.y + point.y,
+                    ),
+                    PinOrigin::BottomLeft(_) => Point::new(
+                        point.x + within.origin.x,
+                        (within.dimensions.height - dimensions.height - point.y) + within.origin.y,
+                    ),
+                    PinOrigin::BottomRight(_) => Point::new(
+                        (within.dimensions.width - dimensions.width - point.x) + within.origin.x,
+                        (within.dimensions.height - dimensions.height - point.y) + within.origin.y,
+                    ),
+                    _ => within.origin.clone(),
+                };
+
+                Rect::new(offset, dimensions)
+            }
+            PinOrigin::Center => {
+                let dimensions = self
+                    .item
+                    .sizing(&within.dimensions)
+                    .resolve(&within.dimensions);
+                let point = Point::new(
+                    (within.dimensions.width - dimensions.width) / 2 + within.origin.x,
+                    (within.dimensions.height - dimensions.height) / 2 + within.origin.y,
+                );
+                Rect::new(point, dimensions)
+            }
+        }
+    }
 }
@@ -0,0 +1,115 @@
+use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
+use crate::values::*;
+use crate::views::View;
+
+/// The least space a view needs before a `Responsive` switches to it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Breakpoint {
+    pub min_width: usize,
+    pub min_height: usize,
+}
+
+impl Breakpoint {
+    pub fn new(min_width: usize, min_height: usize) -> Self {
+        Self {
+            min_width,
+            min_height,
+        }
+    }
+
+    pub fn width(min_width: usize) -> Self {
+        Self::new(min_width, 0)
+    }
+
+    pub fn height(min_height: usize) -> Self {
+        Self::new(0, min_height)
+    }
+
+    pub fn fits(&self, bounds: &Dimensions) -> bool {
+        bounds.width >= self.min_width && bounds.height >= self.min_height
+    }
+}
+
+/// A view which switches between others depending on the space it's given,
+/// e.g. to lay panes out side by side when the terminal is wide, and on top
+/// of each other when it's narrow. The choice is made every time it's sized
+/// or rendered, so it follows the terminal as it's resized.
+///
+/// Breakpoints are checked in reverse order, so the last one added which
+/// fits wins; add them from smallest to largest.
+pub struct Responsive {
+    default: Box<dyn View>,
+    breakpoints: Vec<(Breakpoint, Box<dyn View>)>,
+}
+
+impl Responsive {
+    /// Uses the view when none of the breakpoints fit.
+    pub fn new<V: View>(default: V) -> Self {
+        Self {
+            default: Box::new(default),
+            breakpoints: Vec::new(),
+        }
+    }
+
+    /// Switches to the view when the breakpoint fits.
+    pub fn at<V: View>(mut self, breakpoint: Breakpoint, item: V) -> Self {
+        self.breakpoints.push((breakpoint, Box::new(item)));
+        self
+    }
+
+    fn choose(&self, bounds: &Dimensions) -> &dyn View {
+        self.breakpoints
+            .iter()
+            .rev()
+            .find(|(breakpoint, _)| breakpoint.fits(bounds))
+            .map(|(_, item)| item.as_ref())
+            .unwrap_or(self.default.as_ref())
+    }
+}
+
+impl View for Responsive {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.choose(bounds).sizing(bounds)
+    }
+
+    fn render(&self, within: &Rect, buffer: &mut Buffer) {
+        self.choose(&within.dimensions).render(within, buffer);
+    }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        LayoutNode::of(self, within).children(vec![self.choose(&within.dimensions).inspect(within)])
+    }
+}
+
+#[cfg(test)]
+mod responsive_tests {
+    use super::*;
+    use crate::layouts::Auto;
+    use crate::testing::TestTerminal;
+    use crate::views::Label;
+
+    fn panes() -> Responsive {
+        let panes = |auto: Auto| auto.push(Label::new("one")).push(Label::new("two"));
+        Responsive::new(panes(Auto::vertical()))
+            .at(Breakpoint::width(6), panes(Auto::horizontal()))
+            .at(Breakpoint::new(6, 3), Label::new("tall"))
+    }
+
+    #[test]
+    fn switches_on_size() {
+        TestTerminal::new(5, 2).render(&panes()).assert_text(
+            "
+one
+two",
+        );
+        TestTerminal::new(8, 2).render(&panes()).assert_text(
+            "
+onetwo",
+        );
+        TestTerminal::new(8, 3).render(&panes()).assert_text(
+            "
+tall",
+        );
+    }
+}
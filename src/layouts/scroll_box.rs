@@ -1,4 +1,5 @@
 use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
 use crate::layouts::Auto;
 use crate::values::{Constraints, ContainerSizing, Dimensions, Dir, Point, Rect, Sizing};
 use crate::views::View;
@@ -34,8 +35,8 @@ impl ScrollBox {
         }
     }
 
-    pub fn add<V: View>(mut self, item: V) -> Self {
-        self.contents = self.contents.add(item);
+    pub fn push<V: View>(mut self, item: V) -> Self {
+        self.contents = self.contents.push(item);
         self
     }
 
@@ -60,7 +61,7 @@ impl View for ScrollBox {
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
         match self.dir {
-            Dir::Horizontal => {
+            Dir::Horizontal | Dir::HorizontalReverse => {
                 let content_dimensions = Dimensions::new(MAX_SIZE, within.dimensions.height);
                 let mut content_buffer = Buffer::new(content_dimensions.clone());
                 self.contents.render(
@@ -78,7 +79,7 @@ impl View for ScrollBox {
 
                 buffer.merge(&within.origin, &content_buffer);
             }
-            Dir::Vertical => {
+            Dir::Vertical | Dir::VerticalReverse => {
                 let content_dimensions = Dimensions::new(within.dimensions.width, MAX_SIZE);
                 let mut content_buffer = Buffer::new(content_dimensions.clone());
                 self.contents.render(
@@ -98,4 +99,20 @@ impl View for ScrollBox {
             }
         }
     }
+
+    /// The contents are laid out in their own space, before being scrolled,
+    /// so their rects start from zero rather than the scroll box's origin.
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        let content_dimensions = match self.dir {
+            Dir::Horizontal | Dir::HorizontalReverse => {
+                Dimensions::new(MAX_SIZE, within.dimensions.height)
+            }
+            Dir::Vertical | Dir::VerticalReverse => {
+                Dimensions::new(within.dimensions.width, MAX_SIZE)
+            }
+        };
+        LayoutNode::of(self, within).children(vec![self
+            .contents
+            .inspect(&Rect::new(Point::zero(), content_dimensions))])
+    }
 }
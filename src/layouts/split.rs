@@ -0,0 +1,365 @@
+use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
+use crate::layouts::auto_solver::solve;
+use crate::terminal::{MouseButton, MouseEvent, MouseEventKind};
+use crate::values::*;
+use crate::views::{Rule, View};
+
+/// The sizes of a split's panes, kept in the app's model so they survive the
+/// view being rebuilt, along with any drag of a divider in progress.
+///
+/// Panes share the space by weight, equally to begin with. Moving a divider
+/// splits the weight of the panes either side by their new sizes, so the
+/// panes keep their proportions when the terminal is resized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitState {
+    dir: Dir,
+    weights: Vec<usize>,
+    dragging: Option<usize>,
+}
+
+impl SplitState {
+    /// Panes side by side, divided by vertical lines.
+    pub fn horizontal(panes: usize) -> Self {
+        Self::new(Dir::Horizontal, panes)
+    }
+
+    /// Panes on top of each other, divided by horizontal lines.
+    pub fn vertical(panes: usize) -> Self {
+        Self::new(Dir::Vertical, panes)
+    }
+
+    fn new(dir: Dir, panes: usize) -> Self {
+        Self {
+            dir,
+            weights: vec![1; panes],
+            dragging: None,
+        }
+    }
+
+    /// Starts the panes with these weights, rather than equally.
+    pub fn weights(mut self, weights: Vec<usize>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Drags a divider with the left button, for a split laid out within the
+    /// rect. Returns whether the panes changed size.
+    pub fn mouse(&mut self, event: &MouseEvent, within: &Rect) -> bool {
+        let point = Point::new(event.column as usize, event.row as usize);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let (_, dividers) = arrange(&self.dir, &self.weights, within);
+                self.dragging = dividers.iter().position(|d| d.contains(&point));
+                false
+            }
+            MouseEventKind::Drag(MouseButton::Left) => match self.dragging {
+                Some(divider) => self.move_divider(divider, self.along(&point), within),
+                None => false,
+            },
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.dragging = None;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves a divider by a number of cells, e.g. from the keyboard, where
+    /// negative amounts move it left or up. Returns whether the panes changed
+    /// size.
+    pub fn nudge(&mut self, divider: usize, amount: isize, within: &Rect) -> bool {
+        let (_, dividers) = arrange(&self.dir, &self.weights, within);
+        match dividers.get(divider) {
+            Some(rect) => {
+                let position = self.along(&rect.origin).saturating_add_signed(amount);
+                self.move_divider(divider, position, within)
+            }
+            None => false,
+        }
+    }
+
+    /// Moves a divider to a position, taking space from or giving it to the
+    /// panes either side. Only their weights change, so the other panes keep
+    /// their share even where there's no room to show them.
+    fn move_divider(&mut self, divider: usize, position: usize, within: &Rect) -> bool {
+        let (panes, _) = arrange(&self.dir, &self.weights, within);
+        if divider + 1 >= panes.len() {
+            return false;
+        }
+        let before = self.length(&panes[divider].dimensions);
+        let total = before + self.length(&panes[divider + 1].dimensions);
+        let size = position
+            .saturating_sub(self.along(&panes[divider].origin))
+            .min(total);
+        if total == 0 || size == before {
+            return false;
+        }
+
+        // The pair's weight is split by their new sizes, so it's scaled up
+        // first if it's too coarse to tell the cells apart
+        let mut combined = self.weights[divider] + self.weights[divider + 1];
+        if combined < total {
+            let scale = total.div_ceil(combined.max(1));
+            for weight in self.weights.iter_mut() {
+                *weight *= scale;
+            }
+            combined = combined.max(1) * scale;
+        }
+        self.weights[divider] = (combined * size + total / 2) / total;
+        self.weights[divider + 1] = combined - self.weights[divider];
+        true
+    }
+
+    fn along(&self, point: &Point) -> usize {
+        if self.dir.is_vertical() {
+            point.y
+        } else {
+            point.x
+        }
+    }
+
+    fn length(&self, dimensions: &Dimensions) -> usize {
+        if self.dir.is_vertical() {
+            dimensions.height
+        } else {
+            dimensions.width
+        }
+    }
+}
+
+/// The rects of the panes and of the dividers between them.
+fn arrange(dir: &Dir, weights: &[usize], within: &Rect) -> (Vec<Rect>, Vec<Rect>) {
+    let constraints = |along: Sizing| {
+        if dir.is_vertical() {
+            Constraints::new(Sizing::Fill, along)
+        } else {
+            Constraints::new(along, Sizing::Fill)
+        }
+    };
+    let mut items = Vec::new();
+    for (i, weight) in weights.iter().enumerate() {
+        if i > 0 {
+            items.push(constraints(Sizing::Fixed(1)));
+        }
+        items.push(constraints(Sizing::Weighted(*weight)));
+    }
+
+    let (mut panes, mut dividers) = (Vec::new(), Vec::new());
+    for (i, rect) in solve(&items, dir, &Layout::default(), within)
+        .into_iter()
+        .enumerate()
+    {
+        if i % 2 == 0 {
+            panes.push(rect);
+        } else {
+            dividers.push(rect);
+        }
+    }
+    (panes, dividers)
+}
+
+/// A layout which divides its space between panes, with a line between each
+/// which can be dragged to resize them. The sizes are kept in a
+/// `SplitState`, which the app updates from its mouse and key events.
+///
+/// When hugging, panes count with their minimum size, since the split shares
+/// its space by weight rather than by the size the panes prefer.
+pub struct Split {
+    dir: Dir,
+    weights: Vec<usize>,
+    width: ContainerSizing,
+    height: ContainerSizing,
+    panes: Vec<Box<dyn View>>,
+}
+
+impl Split {
+    /// A split sized by the state. Panes the state doesn't know about get a
+    /// single share of the space.
+    pub fn new(state: &SplitState) -> Self {
+        Self {
+            dir: state.dir.clone(),
+            weights: state.weights.clone(),
+            width: ContainerSizing::Fill,
+            height: ContainerSizing::Fill,
+            panes: Vec::new(),
+        }
+    }
+
+    pub fn push<V: View>(mut self, pane: V) -> Self {
+        self.panes.push(Box::new(pane));
+        self
+    }
+
+    pub fn width(mut self, width: ContainerSizing) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: ContainerSizing) -> Self {
+        self.height = height;
+        self
+    }
+
+    fn arrange(&self, within: &Rect) -> (Vec<Rect>, Vec<Rect>) {
+        let weights: Vec<usize> = (0..self.panes.len())
+            .map(|i| self.weights.get(i).copied().unwrap_or(1))
+            .collect();
+        arrange(&self.dir, &weights, within)
+    }
+
+    /// Works out how large the split is when hugging its panes, from their
+    /// minimums and a cell for each divider.
+    fn measure(&self, bounds: &Dimensions) -> Constraints {
+        let (mut along, mut across) = (self.panes.len().saturating_sub(1), 0);
+        for pane in &self.panes {
+            let mut constraints = pane.sizing(bounds);
+            if self.dir.is_vertical() {
+                constraints.rotate();
+            }
+            along += constraints.width.minimum();
+            across = across.max(constraints.height.minimum());
+        }
+
+        let (width, height) = if self.dir.is_vertical() {
+            (across, along)
+        } else {
+            (along, across)
+        };
+        let content = Dimensions::new(width.min(bounds.width), height.min(bounds.height));
+        Constraints::around(&self.width, &self.height, content)
+    }
+
+    fn divider(&self) -> Rule {
+        if self.dir.is_vertical() {
+            Rule::new(Dir::Horizontal)
+        } else {
+            Rule::new(Dir::Vertical)
+        }
+    }
+}
+
+impl View for Split {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.measure(bounds)
+    }
+
+    fn render(&self, within: &Rect, buffer: &mut Buffer) {
+        let (panes, dividers) = self.arrange(within);
+        for (pane, rect) in self.panes.iter().zip(&panes) {
+            pane.render(rect, buffer);
+        }
+        for rect in &dividers {
+            self.divider().render(rect, buffer);
+        }
+    }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        let (panes, dividers) = self.arrange(within);
+        let divider = self.divider();
+        let children = self
+            .panes
+            .iter()
+            .zip(&panes)
+            .map(|(pane, rect)| pane.inspect(rect))
+            .chain(dividers.iter().map(|rect| divider.inspect(rect)))
+            .collect();
+        LayoutNode::of(self, within).children(children)
+    }
+}
+
+#[cfg(test)]
+mod split_tests {
+    use super::*;
+    use crate::terminal::KeyModifiers;
+    use crate::testing::TestTerminal;
+    use crate::views::Label;
+
+    fn split(state: &SplitState) -> Split {
+        Split::new(state)
+            .push(Label::new("a"))
+            .push(Label::new("b"))
+            .push(Label::new("c"))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn divides_panes() {
+        TestTerminal::new(11, 2)
+            .render(&split(&SplitState::horizontal(3)))
+            .assert_text(
+                "
+a  │b  │c
+   │   │",
+            );
+    }
+
+    #[test]
+    fn drags_dividers() {
+        let within = Rect::new_from_raw(0, 0, 11, 2);
+        let mut state = SplitState::horizontal(3);
+
+        assert!(!state.mouse(&mouse(MouseEventKind::Drag(MouseButton::Left), 1), &within));
+        assert!(!state.mouse(&mouse(MouseEventKind::Down(MouseButton::Left), 3), &within));
+        assert!(state.mouse(&mouse(MouseEventKind::Drag(MouseButton::Left), 1), &within));
+        assert!(!state.mouse(&mouse(MouseEventKind::Up(MouseButton::Left), 1), &within));
+        assert!(!state.mouse(&mouse(MouseEventKind::Drag(MouseButton::Left), 5), &within));
+
+        TestTerminal::new(11, 2).render(&split(&state)).assert_text(
+            "
+a│b    │c
+ │     │",
+        );
+
+        assert!(state.nudge(1, 2, &within));
+        assert!(!state.nudge(2, 1, &within));
+        TestTerminal::new(11, 2).render(&split(&state)).assert_text(
+            "
+a│b      │c
+ │       │",
+        );
+    }
+
+    #[test]
+    fn keeps_weights_without_room() {
+        let mut state = SplitState::horizontal(3);
+        assert!(!state.nudge(0, 1, &Rect::new_from_raw(0, 0, 2, 2)));
+
+        TestTerminal::new(11, 2).render(&split(&state)).assert_text(
+            "
+a  │b  │c
+   │   │",
+        );
+    }
+
+    #[test]
+    fn keeps_other_weights() {
+        let mut state = SplitState::horizontal(3);
+        assert!(state.nudge(0, 1, &Rect::new_from_raw(0, 0, 4, 2)));
+
+        TestTerminal::new(11, 2).render(&split(&state)).assert_text(
+            "
+a     ││c
+      ││",
+        );
+    }
+
+    #[test]
+    fn hugs_panes() {
+        let split = split(&SplitState::horizontal(3))
+            .width(ContainerSizing::Hug)
+            .height(ContainerSizing::Hug);
+        assert_eq!(
+            Constraints::new(Sizing::Fixed(5), Sizing::Fixed(1)),
+            split.sizing(&Dimensions::new(20, 10))
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
+use crate::layouts::cache::SizingCache;
+use crate::values::*;
+use crate::views::View;
+
+/// A layout which layers its children on top of each other, in the order
+/// they're added. Each child is sized within the whole rect and positioned
+/// by its own alignment, e.g. a badge in the top right corner of a panel.
+pub struct Stack {
+    width: ContainerSizing,
+    height: ContainerSizing,
+    layers: Vec<Layer>,
+    cache: SizingCache,
+}
+
+struct Layer {
+    v: Align,
+    h: Align,
+    item: Box<dyn View>,
+}
+
+impl Stack {
+    pub fn new() -> Self {
+        Self {
+            width: ContainerSizing::Fill,
+            height: ContainerSizing::Fill,
+            layers: Vec::new(),
+            cache: SizingCache::default(),
+        }
+    }
+
+    pub fn width(mut self, width: ContainerSizing) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: ContainerSizing) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Adds a layer in the top left corner.
+    pub fn push<V: View>(self, item: V) -> Self {
+        self.push_aligned(Align::Start, Align::Start, item)
+    }
+
+    /// Adds a layer positioned by the vertical and horizontal alignment.
+    pub fn push_aligned<V: View>(mut self, vertical: Align, horizontal: Align, item: V) -> Self {
+        self.layers.push(Layer {
+            v: vertical,
+            h: horizontal,
+            item: Box::new(item),
+        });
+        self
+    }
+
+    pub fn maybe_add<V: View>(self, check: bool, item: V) -> Self {
+        if check {
+            self.push(item)
+        } else {
+            self
+        }
+    }
+
+    /// Measures the layers to work out how large the stack is when hugging
+    /// them.
+    fn measure(&self, bounds: &Dimensions) -> Constraints {
+        let mut width = 0;
+        let mut height = 0;
+        for layer in &self.layers {
+            let constraints = layer.item.sizing(bounds);
+            width = width.max(constraints.preferred.width.clamp(0, bounds.width));
+            height = height.max(constraints.preferred.height.clamp(0, bounds.height));
+        }
+
+        Constraints::around(&self.width, &self.height, Dimensions::new(width, height))
+    }
+}
+
+impl Layer {
+    fn place(&self, within: &Rect) -> Rect {
+        let dimensions = self
+            .item
+            .sizing(&within.dimensions)
+            .resolve(&within.dimensions);
+        let origin = Point::new(
+            within.origin.x + self.h.offset(within.dimensions.width, dimensions.width),
+            within.origin.y + self.v.offset(within.dimensions.height, dimensions.height),
+        );
+        Rect::new(origin, dimensions)
+    }
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for Stack {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        self.cache.measure(bounds, || self.measure(bounds))
+    }
+
+    fn render(&self, within: &Rect, buffer: &mut Buffer) {
+        for layer in &self.layers {
+            layer.item.render(&layer.place(within), buffer);
+        }
+    }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        let children = self
+            .layers
+            .iter()
+            .map(|layer| layer.item.inspect(&layer.place(within)))
+            .collect();
+        LayoutNode::of(self, within)
+            .children(children)
+            .check_hug(self, &self.width, &self.height)
+    }
+}
+
+#[cfg(test)]
+mod stack_tests {
+    use super::*;
+    use crate::styles::Stroke;
+    use crate::testing::TestTerminal;
+    use crate::views::{Border, Label};
+
+    #[test]
+    fn badge_over_panel() {
+        TestTerminal::new(10, 3)
+            .render(
+                &Stack::new()
+                    .push(Border::new(
+                        Stroke::Solid,
+                        Label::new("panel")
+                            .width(ContainerSizing::Fill)
+                            .height(ContainerSizing::Fill),
+                    ))
+                    .push_aligned(Align::Start, Align::End, Label::new("3"))
+                    .push_aligned(Align::End, Align::Center, Label::new("ok")),
+            )
+            .assert_text(
+                "
+┌────────3
+│panel   │
+└───ok───┘",
+            );
+    }
+
+    #[test]
+    fn hugs_largest_layer() {
+        let stack = Stack::new()
+            .width(ContainerSizing::Hug)
+            .height(ContainerSizing::Hug)
+            .push(Label::new("wide label"))
+            .push(Label::new("x"));
+        assert_eq!(
+            Constraints::new(Sizing::Fixed(10), Sizing::Fixed(1)),
+            stack.sizing(&Dimensions::new(20, 5))
+        );
+    }
+}
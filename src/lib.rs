@@ -1,13 +1,27 @@
+mod app;
+mod backend;
 mod buffer;
+mod error;
+mod inspect;
 mod layouts;
+mod spans;
 mod styles;
 mod terminal;
+mod testing;
+mod text;
 mod values;
 mod views;
 
+pub use app::*;
+pub use backend::*;
 pub use buffer::*;
+pub use error::*;
+pub use inspect::*;
 pub use layouts::*;
+pub use spans::*;
 pub use styles::*;
 pub use terminal::*;
+pub use testing::*;
+pub use text::{display_width, grapheme_width, Overflow, Wrap};
 pub use values::*;
 pub use views::*;
@@ -0,0 +1,300 @@
+use crate::styles::Style;
+use crate::text::{display_width, fit, wrap, Overflow, Wrap, ELLIPSIS};
+use std::ops::Range;
+
+/// A run of text sharing a single style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub content: String,
+    pub style: Option<Style>,
+}
+
+impl Span {
+    pub fn raw(content: &str) -> Self {
+        Self {
+            content: content.to_string(),
+            style: None,
+        }
+    }
+
+    pub fn styled(content: &str, style: Style) -> Self {
+        Self {
+            content: content.to_string(),
+            style: Some(style),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        display_width(&self.content)
+    }
+}
+
+/// A single line of text made up of differently styled spans.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Line {
+    pub spans: Vec<Span>,
+}
+
+impl Line {
+    pub fn new() -> Self {
+        Self { spans: Vec::new() }
+    }
+
+    pub fn push(mut self, span: Span) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    pub fn raw(mut self, content: &str) -> Self {
+        self.spans.push(Span::raw(content));
+        self
+    }
+
+    pub fn styled(mut self, content: &str, style: Style) -> Self {
+        self.spans.push(Span::styled(content, style));
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.spans.iter().map(Span::width).sum()
+    }
+
+    /// The content of every span joined together, without styles.
+    pub fn plain(&self) -> String {
+        self.spans.iter().map(|s| s.content.as_str()).collect()
+    }
+
+    /// Shortens the line to fit within the width. An ellipsis takes the
+    /// style of the text it is next to.
+    pub(crate) fn fit(&self, width: usize, overflow: Overflow) -> Line {
+        let plain = self.plain();
+        let fitted = fit(&plain, width, overflow);
+        let mut spans = self.slice(fitted.head.clone());
+        if fitted.ellipsis {
+            let style = if fitted.head.is_empty() {
+                self.style_at(fitted.tail.start)
+            } else {
+                self.style_at(fitted.head.end - 1)
+            };
+            spans.push(Span {
+                content: ELLIPSIS.to_string(),
+                style,
+            });
+        }
+        spans.extend(self.slice(fitted.tail));
+        Line { spans }
+    }
+
+    /// Breaks the line into lines no wider than the width.
+    pub(crate) fn wrap(&self, width: usize, mode: Wrap) -> Vec<Line> {
+        let plain = self.plain();
+        wrap(&plain, width, mode)
+            .into_iter()
+            .map(|piece| {
+                let mut spans = self.slice(piece.range.clone());
+                if piece.hyphen {
+                    spans.push(Span {
+                        content: "-".to_string(),
+                        style: self.style_at(piece.range.end.saturating_sub(1)),
+                    });
+                }
+                Line { spans }
+            })
+            .collect()
+    }
+
+    /// The spans covering a byte range of the plain text.
+    fn slice(&self, range: Range<usize>) -> Vec<Span> {
+        let mut spans = Vec::new();
+        let mut offset = 0;
+        for span in &self.spans {
+            let start = range.start.max(offset);
+            let end = range.end.min(offset + span.content.len());
+            if start < end {
+                spans.push(Span {
+                    content: span.content[(start - offset)..(end - offset)].to_string(),
+                    style: span.style,
+                });
+            }
+            offset += span.content.len();
+        }
+        spans
+    }
+
+    /// The style of the span containing the byte offset of the plain text.
+    fn style_at(&self, index: usize) -> Option<Style> {
+        let mut offset = 0;
+        for span in &self.spans {
+            offset += span.content.len();
+            if index < offset {
+                return span.style;
+            }
+        }
+        None
+    }
+}
+
+impl From<&str> for Line {
+    fn from(content: &str) -> Self {
+        Line::new().raw(content)
+    }
+}
+
+impl From<&String> for Line {
+    fn from(content: &String) -> Self {
+        Line::new().raw(content)
+    }
+}
+
+impl From<String> for Line {
+    fn from(content: String) -> Self {
+        Line::new().push(Span {
+            content,
+            style: None,
+        })
+    }
+}
+
+impl From<Span> for Line {
+    fn from(span: Span) -> Self {
+        Line::new().push(span)
+    }
+}
+
+impl From<Vec<Span>> for Line {
+    fn from(spans: Vec<Span>) -> Self {
+        Line { spans }
+    }
+}
+
+/// Several lines of styled text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Text {
+    pub lines: Vec<Line>,
+}
+
+impl Text {
+    pub fn new() -> Self {
+        Self { lines: Vec::new() }
+    }
+
+    pub fn push<L: Into<Line>>(mut self, line: L) -> Self {
+        self.lines.push(line.into());
+        self
+    }
+
+    /// The width of the widest line.
+    pub fn width(&self) -> usize {
+        self.lines.iter().map(Line::width).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Breaks each line into lines no wider than the width.
+    pub(crate) fn wrap(&self, width: usize, mode: Wrap) -> Vec<Line> {
+        self.lines
+            .iter()
+            .flat_map(|line| line.wrap(width, mode))
+            .collect()
+    }
+}
+
+impl From<&str> for Text {
+    fn from(content: &str) -> Self {
+        Text {
+            lines: content.lines().map(Line::from).collect(),
+        }
+    }
+}
+
+impl From<&String> for Text {
+    fn from(content: &String) -> Self {
+        Text::from(content.as_str())
+    }
+}
+
+impl From<String> for Text {
+    fn from(content: String) -> Self {
+        Text::from(content.as_str())
+    }
+}
+
+impl From<Span> for Text {
+    fn from(span: Span) -> Self {
+        Text::new().push(span)
+    }
+}
+
+impl From<Line> for Text {
+    fn from(line: Line) -> Self {
+        Text::new().push(line)
+    }
+}
+
+impl From<Vec<Line>> for Text {
+    fn from(lines: Vec<Line>) -> Self {
+        Text { lines }
+    }
+}
+
+#[cfg(test)]
+mod span_tests {
+    use super::*;
+    use crate::styles::Color;
+
+    fn bold() -> Style {
+        Style::new().bold()
+    }
+
+    #[test]
+    fn fit_keeps_styles() {
+        let line = Line::new()
+            .raw("find ")
+            .styled("match", bold())
+            .raw(" here");
+        assert_eq!(
+            Line::new()
+                .raw("find ")
+                .styled("ma", bold())
+                .styled("…", bold()),
+            line.fit(8, Overflow::Ellipsis)
+        );
+    }
+
+    #[test]
+    fn wrap_splits_spans() {
+        let red = Style::new().foreground(Color::Red);
+        let line = Line::new().raw("an ").styled("error here", red);
+        assert_eq!(
+            vec![
+                Line::new().raw("an ").styled("error", red),
+                Line::new().styled("here", red),
+            ],
+            line.wrap(8, Wrap::Word)
+        );
+    }
+
+    #[test]
+    fn hyphen_takes_style() {
+        let line = Line::new().styled("abcd", bold());
+        assert_eq!(
+            vec![
+                Line::new().styled("ab", bold()).styled("-", bold()),
+                Line::new().styled("cd", bold()),
+            ],
+            line.wrap(3, Wrap::Hyphenate)
+        );
+    }
+
+    #[test]
+    fn text_keeps_newlines() {
+        let text = Text::from("ab c\nd");
+        assert_eq!(
+            vec![Line::from("ab"), Line::from("c"), Line::from("d")],
+            text.wrap(2, Wrap::Word)
+        );
+        assert_eq!(2, text.wrap(2, Wrap::None).len());
+    }
+}
@@ -11,6 +11,12 @@ pub struct Style {
     pub(crate) style: ContentStyle,
 }
 
+impl Default for Style {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Style {
     pub fn new() -> Self {
         Self {
@@ -22,11 +28,11 @@ impl Style {
     // adding or updating them.
     pub fn update(&mut self, other: &Style) {
         if let Some(color) = other.style.background_color {
-            self.style.background(color);
+            self.style.background_color = Some(color);
         }
 
         if let Some(color) = other.style.foreground_color {
-            self.style.foreground(color);
+            self.style.foreground_color = Some(color);
         }
 
         if !other.style.attributes.is_empty() {
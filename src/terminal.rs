@@ -1,72 +1,212 @@
+use crate::backend::{restore_stdout, Backend, CrosstermBackend};
 use crate::buffer::{Buffer, DiffResult};
-use crate::values::Dimensions;
-use crossterm::{cursor, execute, queue, style, terminal};
-use std::io::{stdout, Stdout, Write};
+use crate::error::{Error, Result};
+use crate::layouts::cache::next_frame;
+use crate::values::{Dimensions, Point, Rect};
+use crate::views::View;
+use crossterm::event::{self, Event};
+pub use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use std::panic;
+use std::time::Duration;
 
-pub struct Terminal {
-    stdout: Stdout,
+pub struct Terminal<B: Backend = CrosstermBackend> {
+    backend: B,
     buffer: Buffer,
 }
 
-impl Terminal {
-    pub fn new() -> Terminal {
-        let mut out = stdout();
-        execute!(out, terminal::EnterAlternateScreen).unwrap();
-        let (cols, rows) = terminal::size().unwrap();
-        let buffer = Buffer::new(Dimensions::new(cols as usize, rows as usize));
+impl Terminal<CrosstermBackend> {
+    pub fn new() -> Result<Self> {
+        Self::with_backend(CrosstermBackend::new())
+    }
+
+    /// Installs a panic hook which restores the terminal before the panic
+    /// message is printed. Without it, the message ends up on the alternate
+    /// screen and is lost once `Drop` runs.
+    pub fn install_panic_hook() {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = restore_stdout();
+            previous(info);
+        }));
+    }
 
-        Terminal {
-            stdout: out,
-            buffer: buffer,
+    /// Blocks until the next input event is available.
+    pub fn next_event(&self) -> Result<TerminalEvent> {
+        Ok(event::read()?.into())
+    }
+
+    /// Waits up to `timeout` for an input event, returning `None` if nothing
+    /// arrived in that time.
+    pub fn poll_event(&self, timeout: Duration) -> Result<Option<TerminalEvent>> {
+        if event::poll(timeout)? {
+            self.next_event().map(Some)
+        } else {
+            Ok(None)
         }
     }
+}
+
+impl<B: Backend> Terminal<B> {
+    pub fn with_backend(backend: B) -> Result<Self> {
+        // Constructed before setup can fail, so `Drop` restores the backend
+        // on the error path as well.
+        let mut term = Terminal {
+            backend,
+            buffer: Buffer::new(Dimensions::zero()),
+        };
+        term.backend.setup()?;
+        term.buffer = term.prepare_buffer()?;
+
+        Ok(term)
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
 
-    pub fn resize(&mut self) -> bool {
-        let (cols, rows) = terminal::size().unwrap();
-        if cols != self.buffer.dimensions.width as u16
-            || rows != self.buffer.dimensions.height as u16
-        {
-            self.buffer = Buffer::new(Dimensions::new(cols as usize, rows as usize));
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    pub fn resize(&mut self) -> Result<bool> {
+        let dimensions = self.backend.size()?;
+        Ok(self.resize_to(dimensions))
+    }
+
+    fn resize_to(&mut self, dimensions: Dimensions) -> bool {
+        if dimensions != self.buffer.dimensions {
+            self.buffer = Buffer::new(dimensions);
             true
         } else {
             false
         }
     }
 
-    pub fn clear(&mut self) {
-        execute!(self.stdout, terminal::Clear(terminal::ClearType::All)).unwrap();
+    pub fn clear(&mut self) -> Result<()> {
+        self.backend.clear()?;
+        // The screen is blank now, so the next update needs to redraw
+        // everything.
+        self.buffer = Buffer::new(self.buffer.dimensions.clone());
+        Ok(())
     }
 
-    pub fn prepare_buffer(&self) -> Buffer {
-        let (cols, rows) = terminal::size().unwrap();
-        Buffer::new(Dimensions::new(cols as usize, rows as usize))
+    pub fn prepare_buffer(&self) -> Result<Buffer> {
+        Ok(Buffer::new(self.backend.size()?))
     }
 
-    pub fn update(&mut self, updates: Buffer) {
-        // TODO: Handle the results from queue and flush.
+    /// Renders the view across the whole terminal and draws the result. If
+    /// the terminal changed size since the last draw, the screen is cleared
+    /// and repainted in full, even when the `Resize` event is still queued.
+    pub fn draw<V: View>(&mut self, view: &V) -> Result<()> {
+        // The size is read once, so the frame always matches the buffer it's
+        // diffed against, even if the terminal is resized meanwhile.
+        let dimensions = self.backend.size()?;
+        if self.resize_to(dimensions.clone()) {
+            self.clear()?;
+        }
+        let mut buffer = Buffer::new(dimensions);
+        let within = Rect::new(Point::zero(), buffer.dimensions.clone());
+        next_frame();
+        view.render(&within, &mut buffer);
+        self.update(buffer)
+    }
+
+    pub fn update(&mut self, updates: Buffer) -> Result<()> {
         match self.buffer.diff(&updates) {
-            DiffResult::NoChange => (),
-            DiffResult::Invalid => (), // Log this as a problem
+            DiffResult::NoChange => Ok(()),
+            DiffResult::Invalid => Err(Error::DimensionMismatch {
+                expected: self.buffer.dimensions.clone(),
+                actual: updates.dimensions,
+            }),
             DiffResult::Changed(changes) => {
-                for (point, cell) in changes {
-                    let content = if let Some(style) = cell.style {
-                        style::StyledContent::new(style.style, cell.content)
-                    } else {
-                        style::style(cell.content)
-                    };
-
-                    queue!(
-                        self.stdout,
-                        cursor::MoveTo(point.x as u16, point.y as u16),
-                        style::PrintStyledContent(content)
-                    )
-                    .unwrap();
-                }
-                self.stdout.flush().unwrap();
+                self.backend.draw(&changes)?;
                 self.buffer = updates;
+                Ok(())
             }
         }
     }
 }
 
-pub enum TerminalEvent {}
+impl<B: Backend> Drop for Terminal<B> {
+    fn drop(&mut self) {
+        let _ = self.backend.restore();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalEvent {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    Resize(Dimensions),
+    FocusGained,
+    FocusLost,
+    Paste(String),
+}
+
+impl From<Event> for TerminalEvent {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Key(key) => TerminalEvent::Key(key),
+            Event::Mouse(mouse) => TerminalEvent::Mouse(mouse),
+            Event::Resize(cols, rows) => {
+                TerminalEvent::Resize(Dimensions::new(cols as usize, rows as usize))
+            }
+            Event::FocusGained => TerminalEvent::FocusGained,
+            Event::FocusLost => TerminalEvent::FocusLost,
+            Event::Paste(text) => TerminalEvent::Paste(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod terminal_tests {
+    use super::*;
+    use crate::backend::MemoryBackend;
+    use crate::buffer::Cell;
+    use crate::views::Label;
+
+    /// Grows a column every time its size is read, as if the terminal were
+    /// being resized continuously.
+    struct Growing {
+        width: std::cell::Cell<usize>,
+        inner: MemoryBackend,
+    }
+
+    impl Backend for Growing {
+        fn size(&self) -> Result<Dimensions> {
+            let width = self.width.get();
+            self.width.set(width + 1);
+            Ok(Dimensions::new(width, 1))
+        }
+
+        fn move_cursor(&mut self, to: &Point) -> Result<()> {
+            self.inner.move_cursor(to)
+        }
+
+        fn write_cell(&mut self, cell: &Cell) -> Result<()> {
+            self.inner.write_cell(cell)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            self.inner.flush()
+        }
+
+        fn clear(&mut self) -> Result<()> {
+            self.inner.clear()
+        }
+    }
+
+    #[test]
+    fn draws_while_resizing() {
+        let mut terminal = Terminal::with_backend(Growing {
+            width: std::cell::Cell::new(2),
+            inner: MemoryBackend::new(Dimensions::new(8, 1)),
+        })
+        .unwrap();
+        terminal.draw(&Label::new("ab")).unwrap();
+        terminal.draw(&Label::new("ab")).unwrap();
+        assert_eq!("b", terminal.backend().inner.buffer().cells[0][1].content);
+    }
+}
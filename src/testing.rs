@@ -0,0 +1,221 @@
+use crate::buffer::Buffer;
+use crate::layouts::cache::next_frame;
+use crate::styles::Style;
+use crate::values::{Dimensions, Point, Rect};
+use crate::views::View;
+
+/// Renders views into an in-memory buffer so they can be checked against
+/// snapshots in tests, without a terminal attached.
+///
+/// Snapshots are multiline strings, one line per row. A single leading
+/// newline is ignored, so a snapshot can start on the line after the opening
+/// quote, and trailing whitespace on each line is ignored, since editors
+/// tend to strip it.
+pub struct TestTerminal {
+    buffer: Buffer,
+}
+
+impl TestTerminal {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            buffer: Buffer::new(Dimensions::new(width, height)),
+        }
+    }
+
+    /// Renders the view into a blank buffer filling the whole terminal.
+    pub fn render<V: View>(&mut self, view: &V) -> &mut Self {
+        let dimensions = self.buffer.dimensions.clone();
+        self.buffer = Buffer::new(dimensions.clone());
+        next_frame();
+        view.render(&Rect::new(Point::zero(), dimensions), &mut self.buffer);
+        self
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// The rendered characters, one line per row. The continuation cells of
+    /// wide characters are skipped, so each line lines up in an editor.
+    pub fn text(&self) -> String {
+        self.buffer
+            .cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.content.as_str())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the styles as a mask, using the character from the legend
+    /// which matches each cell's style. Unstyled cells are shown as spaces
+    /// and styles missing from the legend as `?`.
+    pub fn style_mask(&self, legend: &[(char, Style)]) -> String {
+        self.buffer
+            .cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .filter(|cell| !cell.is_continuation())
+                    .map(|cell| match &cell.style {
+                        None => ' ',
+                        Some(style) => legend
+                            .iter()
+                            .find(|(_, s)| s == style)
+                            .map(|(c, _)| *c)
+                            .unwrap_or('?'),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Panics with a line-by-line diff if the rendered characters don't
+    /// match the snapshot.
+    pub fn assert_text(&self, expected: &str) -> &Self {
+        assert_snapshot("text", &self.text(), expected);
+        self
+    }
+
+    /// Panics with a line-by-line diff if the rendered styles don't match
+    /// the mask. See `style_mask` for how the mask is built.
+    pub fn assert_styles(&self, expected: &str, legend: &[(char, Style)]) -> &Self {
+        assert_snapshot("styles", &self.style_mask(legend), expected);
+        self
+    }
+}
+
+fn snapshot_lines(snapshot: &str, height: usize) -> Vec<String> {
+    let snapshot = snapshot.strip_prefix('\n').unwrap_or(snapshot);
+    let mut lines: Vec<String> = snapshot
+        .split('\n')
+        .map(|line| line.trim_end().to_string())
+        .collect();
+    while lines.len() > height && lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines.resize(height.max(lines.len()), String::new());
+    lines
+}
+
+fn assert_snapshot(kind: &str, actual: &str, expected: &str) {
+    let height = actual.split('\n').count();
+    let actual = snapshot_lines(actual, height);
+    let expected = snapshot_lines(expected, height);
+    if actual == expected {
+        return;
+    }
+
+    let mut report = format!("{} snapshot does not match (- expected, + actual):\n", kind);
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => report.push_str(&format!("  {:>3} |{}|\n", i, a)),
+            (e, a) => {
+                if let Some(e) = e {
+                    report.push_str(&format!("- {:>3} |{}|\n", i, e));
+                }
+                if let Some(a) = a {
+                    report.push_str(&format!("+ {:>3} |{}|\n", i, a));
+                }
+            }
+        }
+    }
+    panic!("{}", report);
+}
+
+#[cfg(test)]
+mod test_terminal_tests {
+    use super::*;
+    use crate::layouts::{Auto, PinBoard, PinOrigin};
+    use crate::styles::{Color, Stroke};
+    use crate::values::Sizing;
+    use crate::views::{Border, Label, Styled};
+
+    #[test]
+    fn border_around_labels() {
+        TestTerminal::new(8, 4)
+            .render(&Border::new(
+                Stroke::Solid,
+                Auto::vertical()
+                    .push(Label::new("One"))
+                    .push(Label::new("Two")),
+            ))
+            .assert_text(
+                "
+┌──────┐
+│One   │
+│Two   │
+└──────┘",
+            );
+    }
+
+    #[test]
+    fn pinned_corners() {
+        TestTerminal::new(6, 3)
+            .render(
+                &PinBoard::new(Sizing::Fill, Sizing::Fill)
+                    .push(PinOrigin::TopLeft(Point::zero()), Label::new("a"))
+                    .push(PinOrigin::BottomRight(Point::zero()), Label::new("b"))
+                    .push(PinOrigin::Center, Label::new("c")),
+            )
+            .assert_text(
+                "
+a
+  c
+     b",
+            );
+    }
+
+    #[test]
+    fn style_mask() {
+        let green = Style::new().background(Color::Green);
+        TestTerminal::new(4, 2)
+            .render(
+                &Auto::vertical()
+                    .push(Styled::new(Label::new("ab")).background(Color::Green))
+                    .push(Label::new("cd")),
+            )
+            .assert_styles(
+                "
+gg",
+                &[('g', green)],
+            );
+    }
+
+    #[test]
+    fn wide_label_in_border() {
+        TestTerminal::new(10, 3)
+            .render(&Border::new(
+                Stroke::Solid,
+                Auto::horizontal()
+                    .push(Label::new("日本語"))
+                    .push(Label::new("!")),
+            ))
+            .assert_text(
+                "
+┌────────┐
+│日本語! │
+└────────┘",
+            );
+    }
+
+    #[test]
+    #[should_panic(expected = "+   0 |One|")]
+    fn mismatch_reports_diff() {
+        TestTerminal::new(3, 1)
+            .render(&Label::new("One"))
+            .assert_text("Two");
+    }
+
+    #[test]
+    #[should_panic(expected = "-   1 |Two|")]
+    fn mismatch_reports_extra_lines() {
+        TestTerminal::new(3, 1)
+            .render(&Label::new("One"))
+            .assert_text("One\nTwo");
+    }
+}
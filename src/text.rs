@@ -0,0 +1,338 @@
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The number of terminal columns a grapheme cluster occupies. Terminals
+/// draw a cluster in at most two cells, even when it's made up of several
+/// wide characters, e.g. emoji joined with zero-width joiners.
+pub fn grapheme_width(grapheme: &str) -> usize {
+    grapheme.width().min(2)
+}
+
+/// The number of terminal columns the text occupies on a single line.
+pub fn display_width(text: &str) -> usize {
+    graphemes(text).map(|(_, width)| width).sum()
+}
+
+/// Splits text into grapheme clusters paired with their display width.
+/// Clusters with no width, like stray control characters, are skipped.
+pub(crate) fn graphemes(text: &str) -> impl Iterator<Item = (&str, usize)> {
+    text.graphemes(true)
+        .map(|g| (g, grapheme_width(g)))
+        .filter(|(_, width)| *width > 0)
+}
+
+/// The longest prefix of the text which fits within the width.
+pub(crate) fn truncate_to_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        used += grapheme_width(grapheme);
+        if used > width {
+            return &text[..index];
+        }
+    }
+    text
+}
+
+/// The longest suffix of the text which fits within the width.
+pub(crate) fn truncate_start_to_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (index, grapheme) in text.grapheme_indices(true).rev() {
+        used += grapheme_width(grapheme);
+        if used > width {
+            return &text[index + grapheme.len()..];
+        }
+    }
+    text
+}
+
+/// What to do with a single line of text that's too wide to fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Cut off whatever doesn't fit at the end.
+    Clip,
+    /// Keep the start, replacing the end with an ellipsis.
+    Ellipsis,
+    /// Keep the end, replacing the start with an ellipsis.
+    LeadingEllipsis,
+    /// Keep both ends, replacing the middle with an ellipsis. Handy for
+    /// paths, where both the root and the file name matter.
+    MiddleEllipsis,
+}
+
+pub(crate) const ELLIPSIS: &str = "…";
+
+/// The parts of a line kept by `fit`, as byte ranges into the line. When
+/// `ellipsis` is set, one goes between the head and the tail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Fitted {
+    pub head: Range<usize>,
+    pub ellipsis: bool,
+    pub tail: Range<usize>,
+}
+
+/// Works out which parts of the line to keep so it fits within the width,
+/// according to the policy.
+pub(crate) fn fit(text: &str, width: usize, overflow: Overflow) -> Fitted {
+    let end = text.len();
+    let keep = |head: &str, ellipsis: bool, tail: &str| Fitted {
+        head: 0..head.len(),
+        ellipsis,
+        tail: (end - tail.len())..end,
+    };
+
+    if display_width(text) <= width {
+        return keep(text, false, "");
+    }
+
+    // Leave room for the ellipsis
+    let available = width.saturating_sub(1);
+    match overflow {
+        _ if width == 0 => keep("", false, ""),
+        Overflow::Clip => keep(truncate_to_width(text, width), false, ""),
+        Overflow::Ellipsis => keep(truncate_to_width(text, available), true, ""),
+        Overflow::LeadingEllipsis => keep("", true, truncate_start_to_width(text, available)),
+        Overflow::MiddleEllipsis => {
+            let head = available.div_ceil(2);
+            keep(
+                truncate_to_width(text, head),
+                true,
+                truncate_start_to_width(text, available - head),
+            )
+        }
+    }
+}
+
+/// How text is broken into lines when it's wider than the space available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    /// Only break on existing newlines, clipping anything too wide.
+    None,
+    /// Break at the last grapheme which fits, even mid-word.
+    Character,
+    /// Break between words, splitting words which are wider than a line.
+    Word,
+    /// Like `Word`, but words which have to be split are hyphenated.
+    Hyphenate,
+}
+
+/// One line produced by `wrap`, as a byte range into the original line,
+/// optionally followed by a hyphen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Piece {
+    pub range: Range<usize>,
+    pub hyphen: bool,
+}
+
+/// Breaks a single line into pieces no wider than `width`, according to the
+/// mode. Whitespace at the point where the line wraps is dropped.
+pub(crate) fn wrap(line: &str, width: usize, mode: Wrap) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    match mode {
+        _ if width == 0 => pieces.push(Piece {
+            range: 0..line.len(),
+            hyphen: false,
+        }),
+        Wrap::None => pieces.push(Piece {
+            range: 0..line.len(),
+            hyphen: false,
+        }),
+        Wrap::Character => split_long(line, 0..line.len(), width, false, &mut pieces),
+        Wrap::Word => wrap_words(line, width, false, &mut pieces),
+        Wrap::Hyphenate => wrap_words(line, width, true, &mut pieces),
+    }
+    pieces
+}
+
+fn wrap_words(line: &str, width: usize, hyphenate: bool, pieces: &mut Vec<Piece>) {
+    let mut current = 0..0;
+    let mut used = 0;
+    let mut started = false;
+    // Set after wrapping, so the spaces at the break aren't carried over
+    let mut wrapped = false;
+
+    let mut offset = 0;
+    for word in line.split(' ') {
+        let word_range = offset..(offset + word.len());
+        offset = word_range.end + 1;
+
+        if wrapped && word.is_empty() {
+            continue;
+        }
+
+        let word_width = display_width(word);
+        if started && used + 1 + word_width <= width {
+            current.end = word_range.end;
+            used += 1 + word_width;
+            wrapped = false;
+            continue;
+        }
+
+        if started {
+            let trimmed = line[current.clone()].trim_end().len();
+            pieces.push(Piece {
+                range: current.start..(current.start + trimmed),
+                hyphen: false,
+            });
+            used = 0;
+            if word.is_empty() {
+                started = false;
+                wrapped = true;
+                continue;
+            }
+        }
+
+        started = true;
+        wrapped = false;
+        if word_width <= width {
+            current = word_range;
+            used = word_width;
+        } else {
+            split_long(line, word_range, width, hyphenate, pieces);
+            // The tail of the word may have room for more after it
+            current = pieces.pop().map(|p| p.range).unwrap_or(0..0);
+            used = display_width(&line[current.clone()]);
+        }
+    }
+
+    pieces.push(Piece {
+        range: current,
+        hyphen: false,
+    });
+}
+
+/// Splits part of a line into pieces no wider than `width`, optionally
+/// hyphenating each piece but the last.
+fn split_long(
+    line: &str,
+    range: Range<usize>,
+    width: usize,
+    hyphenate: bool,
+    pieces: &mut Vec<Piece>,
+) {
+    let limit = if hyphenate && width > 1 {
+        width - 1
+    } else {
+        width
+    };
+
+    let mut start = range.start;
+    while display_width(&line[start..range.end]) > width {
+        let rest = &line[start..range.end];
+        let mut piece = truncate_to_width(rest, limit);
+        if piece.is_empty() {
+            // A wide grapheme which can't fit; take it anyway rather than
+            // looping forever.
+            piece = rest.graphemes(true).next().unwrap_or(rest);
+        }
+        pieces.push(Piece {
+            range: start..(start + piece.len()),
+            hyphen: hyphenate && limit < width,
+        });
+        start += piece.len();
+    }
+    pieces.push(Piece {
+        range: start..range.end,
+        hyphen: false,
+    });
+}
+
+#[cfg(test)]
+mod width_tests {
+    use super::*;
+
+    #[test]
+    fn wide_and_combining() {
+        assert_eq!(5, display_width("hello"));
+        assert_eq!(4, display_width("日本"));
+        assert_eq!(4, display_width("cafe\u{301}"));
+        assert_eq!(2, display_width("👩‍👩‍👧"));
+    }
+
+    #[test]
+    fn truncate_on_grapheme_boundaries() {
+        assert_eq!("日", truncate_to_width("日本", 3));
+        assert_eq!("cafe\u{301}", truncate_to_width("cafe\u{301}s", 4));
+        assert_eq!("", truncate_to_width("日本", 1));
+    }
+}
+
+#[cfg(test)]
+mod overflow_tests {
+    use super::*;
+
+    fn fitted(text: &str, width: usize, overflow: Overflow) -> String {
+        let fitted = fit(text, width, overflow);
+        format!(
+            "{}{}{}",
+            &text[fitted.head],
+            if fitted.ellipsis { ELLIPSIS } else { "" },
+            &text[fitted.tail]
+        )
+    }
+
+    #[test]
+    fn fits() {
+        assert_eq!("short", fitted("short", 5, Overflow::Ellipsis));
+    }
+
+    #[test]
+    fn ellipsis_positions() {
+        let path = "/usr/local/bin/buckle";
+        assert_eq!("/usr/loca", fitted(path, 9, Overflow::Clip));
+        assert_eq!("/usr/loc…", fitted(path, 9, Overflow::Ellipsis));
+        assert_eq!("…n/buckle", fitted(path, 9, Overflow::LeadingEllipsis));
+        assert_eq!("/usr…ckle", fitted(path, 9, Overflow::MiddleEllipsis));
+    }
+
+    #[test]
+    fn respects_display_width() {
+        assert_eq!("日本…", fitted("日本語です", 6, Overflow::Ellipsis));
+        assert_eq!("日…", fitted("日本語です", 4, Overflow::Ellipsis));
+        assert_eq!("…", fitted("日本語です", 1, Overflow::MiddleEllipsis));
+    }
+}
+
+#[cfg(test)]
+mod wrap_tests {
+    use super::*;
+
+    fn wrapped(text: &str, width: usize, mode: Wrap) -> Vec<String> {
+        wrap(text, width, mode)
+            .into_iter()
+            .map(|p| format!("{}{}", &text[p.range], if p.hyphen { "-" } else { "" }))
+            .collect()
+    }
+
+    #[test]
+    fn character() {
+        assert_eq!(
+            vec!["abc", "def", "g"],
+            wrapped("abcdefg", 3, Wrap::Character)
+        );
+        assert_eq!(vec!["日", "本"], wrapped("日本", 3, Wrap::Character));
+    }
+
+    #[test]
+    fn word() {
+        assert_eq!(
+            vec!["the quick", "brown fox"],
+            wrapped("the quick brown fox", 10, Wrap::Word)
+        );
+        assert_eq!(vec!["a", "b"], wrapped("a   b", 2, Wrap::Word));
+        assert_eq!(vec!["  indented"], wrapped("  indented", 10, Wrap::Word));
+    }
+
+    #[test]
+    fn word_longer_than_line() {
+        assert_eq!(vec!["abcdef", "g hi"], wrapped("abcdefg hi", 6, Wrap::Word));
+    }
+
+    #[test]
+    fn hyphenate() {
+        assert_eq!(
+            vec!["ab-", "cd-", "efg", "hi"],
+            wrapped("abcdefg hi", 3, Wrap::Hyphenate)
+        );
+    }
+}
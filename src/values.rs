@@ -69,43 +69,309 @@ impl Rect {
         self.origin.rotate();
         self.dimensions.rotate();
     }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= self.origin.x
+            && point.y >= self.origin.y
+            && point.x < self.origin.x + self.dimensions.width
+            && point.y < self.origin.y + self.dimensions.height
+    }
 }
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Sizing {
     Fill,
     Fixed(usize),
+    /// Shares the remaining space like `Fill`, but is never given less than
+    /// `min` or more than `max`.
+    Bounded {
+        min: usize,
+        max: usize,
+    },
+    /// Shares the remaining space like `Fill`, taking this many shares of it
+    /// relative to the other fills. `Fill` counts as a single share.
+    Weighted(usize),
+    /// A percentage of the parent's size, clamped to what's left.
+    Percent(usize),
+    /// The size of the other axis, multiplied by `numerator / denominator`,
+    /// e.g. to keep an aspect ratio. Where the other axis isn't known, it
+    /// takes the space available like `Fill`.
+    Ratio {
+        numerator: usize,
+        denominator: usize,
+        /// Space on the other axis that isn't part of the ratio, e.g. a
+        /// margin, taken off before this axis is derived from it.
+        other_extra: usize,
+        /// Space on this axis that isn't part of the ratio, added on after.
+        extra: usize,
+    },
 }
 
 impl Sizing {
+    pub fn ratio(numerator: usize, denominator: usize) -> Self {
+        Sizing::Ratio {
+            numerator,
+            denominator,
+            other_extra: 0,
+            extra: 0,
+        }
+    }
+
     pub fn constrain_by(&self, max: usize) -> Self {
         match self {
             Sizing::Fill => Sizing::Fill,
             Sizing::Fixed(n) => Sizing::Fixed(*n.clamp(&0, &max)),
+            Sizing::Bounded { min, max: upper } => Sizing::Bounded {
+                min: *min.min(&max),
+                max: *upper.min(&max),
+            },
+            Sizing::Weighted(weight) => Sizing::Weighted(*weight),
+            Sizing::Percent(percent) => Sizing::Percent(*percent),
+            Sizing::Ratio { .. } => *self,
+        }
+    }
+
+    /// How many shares of the remaining space the item takes, if it shares
+    /// the space with other fills.
+    pub fn weight(&self) -> Option<usize> {
+        match self {
+            Sizing::Fill | Sizing::Bounded { .. } => Some(1),
+            Sizing::Weighted(weight) => Some(*weight),
+            Sizing::Fixed(_) | Sizing::Percent(_) | Sizing::Ratio { .. } => None,
+        }
+    }
+
+    /// Sets a lower bound on a fill. Fixed sizes are grown to meet it. Any
+    /// weight, percentage or ratio is replaced by a plain bounded fill.
+    pub fn min(self, min: usize) -> Self {
+        match self {
+            Sizing::Fill | Sizing::Weighted(_) | Sizing::Percent(_) | Sizing::Ratio { .. } => {
+                Sizing::Bounded {
+                    min,
+                    max: usize::MAX,
+                }
+            }
+            Sizing::Fixed(n) => Sizing::Fixed(n.max(min)),
+            Sizing::Bounded { max, .. } => Sizing::Bounded {
+                min,
+                max: max.max(min),
+            },
+        }
+    }
+
+    /// Sets an upper bound on a fill. Fixed sizes are shrunk to meet it. Any
+    /// weight, percentage or ratio is replaced by a plain bounded fill.
+    pub fn max(self, max: usize) -> Self {
+        match self {
+            Sizing::Fill | Sizing::Weighted(_) | Sizing::Percent(_) | Sizing::Ratio { .. } => {
+                Sizing::Bounded { min: 0, max }
+            }
+            Sizing::Fixed(n) => Sizing::Fixed(n.min(max)),
+            Sizing::Bounded { min, .. } => Sizing::Bounded {
+                min: min.min(max),
+                max,
+            },
+        }
+    }
+
+    /// Adds a fixed amount to the size and its bounds, e.g. for padding or
+    /// borders around an item. A fill grows a minimum, so it's never given
+    /// less room than the amount added. A ratio adds it after deriving the
+    /// size, so it isn't scaled.
+    pub fn grow(self, amount: usize) -> Self {
+        match self {
+            Sizing::Fill if amount > 0 => Sizing::Bounded {
+                min: amount,
+                max: usize::MAX,
+            },
+            Sizing::Ratio {
+                numerator,
+                denominator,
+                other_extra,
+                extra,
+            } => Sizing::Ratio {
+                numerator,
+                denominator,
+                other_extra,
+                extra: extra.saturating_add(amount),
+            },
+            Sizing::Fill | Sizing::Weighted(_) | Sizing::Percent(_) => self,
+            Sizing::Fixed(n) => Sizing::Fixed(n + amount),
+            Sizing::Bounded { min, max } => Sizing::Bounded {
+                min: min + amount,
+                max: max.saturating_add(amount),
+            },
+        }
+    }
+
+    /// The least space the item can be given without being truncated.
+    pub fn minimum(&self) -> usize {
+        match self {
+            Sizing::Fill | Sizing::Weighted(_) | Sizing::Percent(_) | Sizing::Ratio { .. } => 0,
+            Sizing::Fixed(n) => *n,
+            Sizing::Bounded { min, .. } => *min,
+        }
+    }
+
+    /// Clamps a size to what the sizing allows.
+    fn allow(&self, size: usize) -> usize {
+        match self {
+            Sizing::Fixed(n) => *n,
+            Sizing::Bounded { min, max } => size.clamp(*min, (*max).max(*min)),
+            Sizing::Fill | Sizing::Weighted(_) | Sizing::Percent(_) | Sizing::Ratio { .. } => size,
+        }
+    }
+
+    /// Resolves the size given the space available to it.
+    pub fn resolve(&self, available: usize) -> usize {
+        match self {
+            Sizing::Fill | Sizing::Weighted(_) | Sizing::Ratio { .. } => available,
+            Sizing::Fixed(n) => (*n).min(available),
+            Sizing::Percent(percent) => available * (*percent).min(100) / 100,
+            Sizing::Bounded { min, max } => available.min(*max).max(*min).min(available),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ContainerSizing {
     Hug,
     Fill,
     Fixed(usize),
+    /// Hugs the contents, but stays within the bounds.
+    HugBounded {
+        min: usize,
+        max: usize,
+    },
+    /// Fills the space available, but stays within the bounds.
+    FillBounded {
+        min: usize,
+        max: usize,
+    },
+    /// Fills the space available, taking this many shares of it.
+    Weighted(usize),
+    /// A percentage of the parent's size.
+    Percent(usize),
 }
 
 impl ContainerSizing {
+    /// Whether the size depends on the contents.
+    pub fn hugs(&self) -> bool {
+        matches!(
+            self,
+            ContainerSizing::Hug | ContainerSizing::HugBounded { .. }
+        )
+    }
+
     pub fn simplify(&self, hug_value: usize) -> Sizing {
         match self {
             ContainerSizing::Hug => Sizing::Fixed(hug_value),
             ContainerSizing::Fill => Sizing::Fill,
             ContainerSizing::Fixed(size) => Sizing::Fixed(*size),
+            ContainerSizing::HugBounded { min, max } => {
+                Sizing::Fixed(hug_value.clamp(*min, (*max).max(*min)))
+            }
+            ContainerSizing::FillBounded { min, max } => Sizing::Bounded {
+                min: *min,
+                max: (*max).max(*min),
+            },
+            ContainerSizing::Weighted(weight) => Sizing::Weighted(*weight),
+            ContainerSizing::Percent(percent) => Sizing::Percent(*percent),
+        }
+    }
+
+    /// Sets a lower bound on hugging or filling. Fixed sizes are grown to
+    /// meet it.
+    pub fn min(self, min: usize) -> Self {
+        match self {
+            ContainerSizing::Hug => ContainerSizing::HugBounded {
+                min,
+                max: usize::MAX,
+            },
+            ContainerSizing::Fill | ContainerSizing::Weighted(_) | ContainerSizing::Percent(_) => {
+                ContainerSizing::FillBounded {
+                    min,
+                    max: usize::MAX,
+                }
+            }
+            ContainerSizing::Fixed(n) => ContainerSizing::Fixed(n.max(min)),
+            ContainerSizing::HugBounded { max, .. } => ContainerSizing::HugBounded {
+                min,
+                max: max.max(min),
+            },
+            ContainerSizing::FillBounded { max, .. } => ContainerSizing::FillBounded {
+                min,
+                max: max.max(min),
+            },
+        }
+    }
+
+    /// Sets an upper bound on hugging or filling. Fixed sizes are shrunk to
+    /// meet it.
+    pub fn max(self, max: usize) -> Self {
+        match self {
+            ContainerSizing::Hug => ContainerSizing::HugBounded { min: 0, max },
+            ContainerSizing::Fill | ContainerSizing::Weighted(_) | ContainerSizing::Percent(_) => {
+                ContainerSizing::FillBounded { min: 0, max }
+            }
+            ContainerSizing::Fixed(n) => ContainerSizing::Fixed(n.min(max)),
+            ContainerSizing::HugBounded { min, .. } => ContainerSizing::HugBounded {
+                min: min.min(max),
+                max,
+            },
+            ContainerSizing::FillBounded { min, .. } => ContainerSizing::FillBounded {
+                min: min.min(max),
+                max,
+            },
         }
     }
 }
 
+/// The direction a layout places its children in. The reversed directions
+/// place the first child at the right or bottom, mirroring the whole layout
+/// along its axis, including the alignment on it.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Dir {
     Horizontal,
     Vertical,
+    HorizontalReverse,
+    VerticalReverse,
+}
+
+/// Which way text runs in a script, e.g. right to left for Arabic and Hebrew.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Dir {
+    /// The horizontal direction which follows the text, so layouts mirror
+    /// for right to left scripts.
+    pub fn inline(text: TextDirection) -> Self {
+        match text {
+            TextDirection::LeftToRight => Dir::Horizontal,
+            TextDirection::RightToLeft => Dir::HorizontalReverse,
+        }
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, Dir::Vertical | Dir::VerticalReverse)
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        matches!(self, Dir::HorizontalReverse | Dir::VerticalReverse)
+    }
+
+    /// The same axis, in the opposite direction.
+    pub fn reverse(&self) -> Self {
+        match self {
+            Dir::Horizontal => Dir::HorizontalReverse,
+            Dir::Vertical => Dir::VerticalReverse,
+            Dir::HorizontalReverse => Dir::Horizontal,
+            Dir::VerticalReverse => Dir::Vertical,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -115,11 +381,106 @@ pub enum Align {
     End,
 }
 
+impl Align {
+    /// How far from the start an item of the size sits within the space
+    /// available.
+    pub fn offset(&self, available: usize, size: usize) -> usize {
+        let spare = available.saturating_sub(size);
+        match self {
+            Align::Start => 0,
+            Align::Center => spare / 2,
+            Align::End => spare,
+        }
+    }
+}
+
+/// Space kept clear around an item by the layout it's in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Margin {
+    pub top: usize,
+    pub bottom: usize,
+    pub left: usize,
+    pub right: usize,
+}
+
+impl Margin {
+    pub fn new(left: usize, right: usize, top: usize, bottom: usize) -> Self {
+        Self {
+            top,
+            bottom,
+            left,
+            right,
+        }
+    }
+
+    pub fn all(value: usize) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    pub fn vertical(value: usize) -> Self {
+        Self::new(0, 0, value, value)
+    }
+
+    pub fn horizontal(value: usize) -> Self {
+        Self::new(value, value, 0, 0)
+    }
+
+    /// Grows the constraints to make room for the margin.
+    pub fn grow(&self, constraints: &Constraints) -> Constraints {
+        constraints.grow(self.left + self.right, self.top + self.bottom)
+    }
+
+    /// Swaps the sides along a reversed direction's axis, so the margin
+    /// follows the items' order the way their positions do.
+    pub fn mirror(&self, dir: &Dir) -> Self {
+        match dir {
+            Dir::HorizontalReverse => Self::new(self.right, self.left, self.top, self.bottom),
+            Dir::VerticalReverse => Self::new(self.left, self.right, self.bottom, self.top),
+            Dir::Horizontal | Dir::Vertical => *self,
+        }
+    }
+
+    /// Shrinks the rect to the part inside the margin.
+    pub fn inset(&self, rect: &Rect) -> Rect {
+        Rect::new(
+            Point::new(rect.origin.x + self.left, rect.origin.y + self.top),
+            Dimensions::new(
+                rect.dimensions.width.saturating_sub(self.left + self.right),
+                rect.dimensions
+                    .height
+                    .saturating_sub(self.top + self.bottom),
+            ),
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Layout {
-    Packed { v: Align, h: Align },
-    Spaced { v: Align, h: Align, spacing: usize },
-    Spread { v: Align, h: Align },
+    Packed {
+        v: Align,
+        h: Align,
+    },
+    Spaced {
+        v: Align,
+        h: Align,
+        spacing: usize,
+    },
+    Spread {
+        v: Align,
+        h: Align,
+    },
+    /// Spreads the space so each item has the same amount either side, so
+    /// the gaps at the ends are half those between items.
+    SpaceAround {
+        v: Align,
+        h: Align,
+    },
+    /// Spreads the space so the gaps at the ends and between items are all
+    /// the same.
+    SpaceEvenly {
+        v: Align,
+        h: Align,
+    },
 }
 
 impl Default for Layout {
@@ -154,6 +515,20 @@ impl Layout {
         }
     }
 
+    pub fn around(vertical: Align, horizontal: Align) -> Self {
+        Self::SpaceAround {
+            v: vertical,
+            h: horizontal,
+        }
+    }
+
+    pub fn evenly(vertical: Align, horizontal: Align) -> Self {
+        Self::SpaceEvenly {
+            v: vertical,
+            h: horizontal,
+        }
+    }
+
     pub fn vertical(&self) -> &Align {
         match self {
             Layout::Packed { v: vertical, h: _ } => vertical,
@@ -162,7 +537,9 @@ impl Layout {
                 h: _,
                 spacing: _,
             } => vertical,
-            Layout::Spread { v: vertical, h: _ } => vertical,
+            Layout::Spread { v: vertical, h: _ }
+            | Layout::SpaceAround { v: vertical, h: _ }
+            | Layout::SpaceEvenly { v: vertical, h: _ } => vertical,
         }
     }
 
@@ -180,6 +557,14 @@ impl Layout {
             Layout::Spread {
                 v: _,
                 h: horizontal,
+            }
+            | Layout::SpaceAround {
+                v: _,
+                h: horizontal,
+            }
+            | Layout::SpaceEvenly {
+                v: _,
+                h: horizontal,
             } => horizontal,
         }
     }
@@ -210,14 +595,77 @@ impl Layout {
                 v: *horizontal,
                 h: *vertical,
             },
+            Self::SpaceAround {
+                v: vertical,
+                h: horizontal,
+            } => Self::SpaceAround {
+                v: *horizontal,
+                h: *vertical,
+            },
+            Self::SpaceEvenly {
+                v: vertical,
+                h: horizontal,
+            } => Self::SpaceEvenly {
+                v: *horizontal,
+                h: *vertical,
+            },
         }
     }
 }
 
+/// Fixed space along a layout's axis on top of its `Layout`: padding before
+/// the first item and after the last, and gaps between particular items in
+/// place of the layout's own spacing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Gaps {
+    leading: usize,
+    trailing: usize,
+    after: Vec<(usize, usize)>,
+}
+
+impl Gaps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Padding before the first item.
+    pub fn leading(mut self, size: usize) -> Self {
+        self.leading = size;
+        self
+    }
+
+    /// Padding after the last item.
+    pub fn trailing(mut self, size: usize) -> Self {
+        self.trailing = size;
+        self
+    }
+
+    /// The gap between the item at the index and the next one.
+    pub fn after(mut self, index: usize, size: usize) -> Self {
+        self.after.retain(|(i, _)| *i != index);
+        self.after.push((index, size));
+        self
+    }
+
+    pub(crate) fn padding(&self) -> (usize, usize) {
+        (self.leading, self.trailing)
+    }
+
+    pub(crate) fn gap_after(&self, index: usize) -> Option<usize> {
+        self.after
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, size)| *size)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Constraints {
     pub width: Sizing,
     pub height: Sizing,
+    /// The size the item would like, e.g. to fit its content, which hugging
+    /// containers size it to where there's room. Defaults to the minimums.
+    pub preferred: Dimensions,
 }
 
 impl Constraints {
@@ -226,9 +674,102 @@ impl Constraints {
         let height = self.height;
         self.width = height;
         self.height = width;
+        self.preferred.rotate();
     }
 
     pub fn new(width: Sizing, height: Sizing) -> Self {
-        Self { width, height }
+        Self {
+            width,
+            height,
+            preferred: Dimensions::new(width.minimum(), height.minimum()),
+        }
+    }
+
+    /// A container's constraints, sized to its content where it hugs, and
+    /// preferring the content's size either way.
+    pub fn around(width: &ContainerSizing, height: &ContainerSizing, content: Dimensions) -> Self {
+        Self::new(
+            width.simplify(content.width),
+            height.simplify(content.height),
+        )
+        .prefer(content)
+    }
+
+    /// Sets the preferred size, within what each axis's sizing allows.
+    pub fn prefer(mut self, preferred: Dimensions) -> Self {
+        self.preferred = Dimensions::new(
+            self.width.allow(preferred.width),
+            self.height.allow(preferred.height),
+        );
+        self
+    }
+
+    /// Grows each axis by a fixed amount, e.g. for a margin or border around
+    /// an item. A ratio stays between the item's own sizes, so the growth of
+    /// the other axis is taken off before the ratio is applied.
+    pub fn grow(&self, width: usize, height: usize) -> Self {
+        let grow = |sizing: Sizing, amount: usize, other: usize| match sizing {
+            Sizing::Ratio {
+                numerator,
+                denominator,
+                other_extra,
+                extra,
+            } => Sizing::Ratio {
+                numerator,
+                denominator,
+                other_extra: other_extra.saturating_add(other),
+                extra: extra.saturating_add(amount),
+            },
+            sizing => sizing.grow(amount),
+        };
+        Self {
+            width: grow(self.width, width, height),
+            height: grow(self.height, height, width),
+            preferred: Dimensions::new(
+                self.preferred.width.saturating_add(width),
+                self.preferred.height.saturating_add(height),
+            ),
+        }
+    }
+
+    /// Resolves the size of both axes given the space available, deriving
+    /// an axis sized by `Ratio` from the other. If the derived size doesn't
+    /// fit, the other axis is shrunk to keep the ratio.
+    pub fn resolve(&self, available: &Dimensions) -> Dimensions {
+        let mut constraints = self.clone();
+        let mut available = available.clone();
+        let rotate = matches!(self.width, Sizing::Ratio { .. });
+        if rotate {
+            constraints.rotate();
+            available.rotate();
+        }
+
+        let mut dimensions = Dimensions::new(
+            constraints.width.resolve(available.width),
+            constraints.height.resolve(available.height),
+        );
+        if let Sizing::Ratio {
+            numerator,
+            denominator,
+            other_extra,
+            extra,
+        } = constraints.height
+        {
+            dimensions.height = dimensions.width.saturating_sub(other_extra) * numerator
+                / denominator.max(1)
+                + extra;
+            if dimensions.height > available.height {
+                dimensions.height = available.height;
+                dimensions.width = dimensions.width.min(
+                    available.height.saturating_sub(extra) * denominator / numerator.max(1)
+                        + other_extra,
+                );
+            }
+        }
+
+        if rotate {
+            dimensions.rotate();
+        }
+        dimensions
     }
 }
@@ -1,15 +1,31 @@
 use crate::buffer::Buffer;
+use crate::inspect::LayoutNode;
+use crate::spans::{Line, Text};
 use crate::styles::{Stroke, Style};
+use crate::text::{Overflow, Wrap};
 use crate::values::{Constraints, ContainerSizing, Dimensions, Dir, Point, Rect, Sizing};
 use crossterm::style::Color;
 
 pub trait View: 'static {
     fn sizing(&self, bounds: &Dimensions) -> Constraints;
     fn render(&self, within: &Rect, buffer: &mut Buffer);
+
+    /// Works out where the view and its children would be rendered, without
+    /// rendering anything. Views containing others should override this so
+    /// their children show up in the tree.
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        LayoutNode::of(self, within)
+    }
 }
 
 pub struct Spacer;
 
+impl Default for Spacer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Spacer {
     pub fn new() -> Self {
         Self {}
@@ -18,30 +34,35 @@ impl Spacer {
 
 impl View for Spacer {
     fn sizing(&self, _: &Dimensions) -> Constraints {
-        Constraints {
-            width: Sizing::Fill,
-            height: Sizing::Fill,
-        }
+        Constraints::new(Sizing::Fill, Sizing::Fill)
     }
 
     fn render(&self, _: &Rect, _: &mut Buffer) {}
 }
 
 pub struct Label {
-    pub text: String,
+    pub text: Line,
     pub width: ContainerSizing,
     pub height: ContainerSizing,
+    pub overflow: Overflow,
 }
 
 impl Label {
-    pub fn new(text: &str) -> Self {
+    /// Accepts plain strings, or a `Line` of styled spans.
+    pub fn new<T: Into<Line>>(text: T) -> Self {
         Self {
-            text: text.to_string(),
+            text: text.into(),
             width: ContainerSizing::Hug,
             height: ContainerSizing::Hug,
+            overflow: Overflow::Clip,
         }
     }
 
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
     pub fn width(mut self, width: ContainerSizing) -> Self {
         self.width = width;
         self
@@ -55,40 +76,40 @@ impl Label {
 
 impl View for Label {
     fn sizing(&self, bounds: &Dimensions) -> Constraints {
-        Constraints {
-            width: self
-                .width
-                .simplify(self.text.chars().count().clamp(0, bounds.width)),
-            height: self.height.simplify(1),
-        }
+        let width = self.text.width().clamp(0, bounds.width);
+        Constraints::around(&self.width, &self.height, Dimensions::new(width, 1))
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
-        if self.text.chars().count() > within.dimensions.width {
-            let mut prefix = self.text.clone();
-            prefix.truncate(within.dimensions.width);
-            buffer.draw_text(&within.origin, &prefix);
-        } else {
-            buffer.draw_text(&within.origin, &self.text);
-        }
+        let visible = self.text.fit(within.dimensions.width, self.overflow);
+        buffer.draw_spans(&within.origin, &visible.spans);
     }
 }
 
 pub struct MultilineText {
-    pub text: String,
+    pub text: Text,
     pub width: ContainerSizing,
     pub height: ContainerSizing,
+    pub wrap: Wrap,
 }
 
 impl MultilineText {
-    pub fn new(text: &str) -> Self {
+    /// Accepts plain strings, split on newlines, or `Text` made up of lines
+    /// of styled spans.
+    pub fn new<T: Into<Text>>(text: T) -> Self {
         Self {
-            text: text.to_string(),
+            text: text.into(),
             width: ContainerSizing::Hug,
             height: ContainerSizing::Hug,
+            wrap: Wrap::None,
         }
     }
 
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
     pub fn width(mut self, width: ContainerSizing) -> Self {
         self.width = width;
         self
@@ -102,23 +123,27 @@ impl MultilineText {
 
 impl View for MultilineText {
     fn sizing(&self, bounds: &Dimensions) -> Constraints {
-        Constraints {
-            width: self.width.simplify(
-                self.text
-                    .lines()
-                    .max()
-                    .map(|l| l.chars().count())
-                    .unwrap_or(0)
-                    .clamp(0, bounds.width),
-            ),
-            height: self
-                .height
-                .simplify(self.text.lines().count().clamp(0, bounds.height)),
-        }
+        // The text flows to fit the widest it could be given, within its own
+        // bounds, so it's never measured wider than it's later rendered
+        let wrap_width = self.width.simplify(bounds.width).resolve(bounds.width);
+        let lines = self.text.wrap(wrap_width, self.wrap);
+        let width = lines
+            .iter()
+            .map(Line::width)
+            .max()
+            .unwrap_or(0)
+            .clamp(0, bounds.width);
+        let height = lines.len().clamp(0, bounds.height);
+        Constraints::around(&self.width, &self.height, Dimensions::new(width, height))
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
-        buffer.draw_multiline_text(&within, &self.text);
+        let lines = self.text.wrap(within.dimensions.width, self.wrap);
+        for (offset, line) in lines.iter().take(within.dimensions.height).enumerate() {
+            let visible = line.fit(within.dimensions.width, Overflow::Clip);
+            let at = Point::new(within.origin.x, within.origin.y + offset);
+            buffer.draw_spans(&at, &visible.spans);
+        }
     }
 }
 
@@ -135,21 +160,24 @@ impl Rule {
 impl View for Rule {
     fn sizing(&self, _: &Dimensions) -> Constraints {
         match self.dir {
-            Dir::Horizontal => Constraints {
-                height: Sizing::Fixed(1),
-                width: Sizing::Fill,
-            },
-            Dir::Vertical => Constraints {
-                height: Sizing::Fill,
-                width: Sizing::Fixed(1),
-            },
+            // Always at least a character long, so it shows up when hugged
+            Dir::Horizontal | Dir::HorizontalReverse => {
+                Constraints::new(Sizing::Fill.min(1), Sizing::Fixed(1))
+            }
+            Dir::Vertical | Dir::VerticalReverse => {
+                Constraints::new(Sizing::Fixed(1), Sizing::Fill.min(1))
+            }
         }
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
         match self.dir {
-            Dir::Horizontal => buffer.draw_h_rule(&within.origin, within.dimensions.width),
-            Dir::Vertical => buffer.draw_v_rule(&within.origin, within.dimensions.height),
+            Dir::Horizontal | Dir::HorizontalReverse => {
+                buffer.draw_h_rule(&within.origin, within.dimensions.width)
+            }
+            Dir::Vertical | Dir::VerticalReverse => {
+                buffer.draw_v_rule(&within.origin, within.dimensions.height)
+            }
         }
     }
 }
@@ -212,6 +240,17 @@ impl Padding {
             item: Box::new(item),
         }
     }
+
+    /// Offsets the rect and reduces its size.
+    fn inner(&self, within: &Rect) -> Rect {
+        Rect::new(
+            Point::new(within.origin.x + self.left, within.origin.y + self.top),
+            Dimensions::new(
+                within.dimensions.width - self.left - self.right,
+                within.dimensions.height - self.top - self.bottom,
+            ),
+        )
+    }
 }
 
 impl View for Padding {
@@ -220,30 +259,17 @@ impl View for Padding {
             bounds.width - self.left - self.right,
             bounds.height - self.top - self.bottom,
         );
-        let constraints = self.item.sizing(&remaining);
-        Constraints::new(
-            match constraints.width {
-                Sizing::Fill => Sizing::Fill,
-                Sizing::Fixed(n) => Sizing::Fixed(n + self.left + self.right),
-            },
-            match constraints.height {
-                Sizing::Fill => Sizing::Fill,
-                Sizing::Fixed(n) => Sizing::Fixed(n + self.top + self.bottom),
-            },
-        )
+        self.item
+            .sizing(&remaining)
+            .grow(self.left + self.right, self.top + self.bottom)
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
-        // Offset the rect and reduce it's size
-        let offset = Rect::new(
-            Point::new(within.origin.x + self.left, within.origin.y + self.top),
-            Dimensions::new(
-                within.dimensions.width - self.left - self.right,
-                within.dimensions.height - self.top - self.bottom,
-            ),
-        );
+        self.item.render(&self.inner(within), buffer);
+    }
 
-        self.item.render(&offset, buffer);
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        LayoutNode::of(self, within).children(vec![self.item.inspect(&self.inner(within))])
     }
 }
 
@@ -298,13 +324,17 @@ impl Styled {
 
 impl View for Styled {
     fn sizing(&self, bounds: &Dimensions) -> Constraints {
-        self.item.sizing(&bounds)
+        self.item.sizing(bounds)
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
         buffer.merge_style(within, &self.style);
         self.item.render(within, buffer)
     }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        LayoutNode::of(self, within).children(vec![self.item.inspect(within)])
+    }
 }
 
 pub struct Border {
@@ -331,31 +361,103 @@ impl Border {
         self.style = self.style.background(color);
         self
     }
+
+    fn inner(within: &Rect) -> Rect {
+        let mut within = within.clone();
+        within.origin.x += 1;
+        within.origin.y += 1;
+        within.dimensions.width -= 2;
+        within.dimensions.height -= 2;
+        within
+    }
 }
 
 impl View for Border {
     fn sizing(&self, bounds: &Dimensions) -> Constraints {
-        let sizing = self.item.sizing(&bounds);
+        let sizing = self.item.sizing(bounds).grow(2, 2);
         Constraints::new(
-            match sizing.width {
-                Sizing::Fill => Sizing::Fill,
-                Sizing::Fixed(n) => Sizing::Fixed((n + 2).clamp(0, bounds.width)),
-            },
-            match sizing.height {
-                Sizing::Fill => Sizing::Fill,
-                Sizing::Fixed(n) => Sizing::Fixed((n + 2).clamp(0, bounds.height)),
-            },
+            sizing.width.constrain_by(bounds.width),
+            sizing.height.constrain_by(bounds.height),
         )
+        .prefer(sizing.preferred)
     }
 
     fn render(&self, within: &Rect, buffer: &mut Buffer) {
-        let mut within = within.clone();
-        buffer.draw_box(&within, &self.stroke, &Some(self.style));
-        within.origin.x += 1;
-        within.origin.y += 1;
-        within.dimensions.width -= 2;
-        within.dimensions.height -= 2;
-        self.item.render(&within, buffer);
+        buffer.draw_box(within, &self.stroke, &Some(self.style));
+        self.item.render(&Self::inner(within), buffer);
+    }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        LayoutNode::of(self, within).children(vec![self.item.inspect(&Self::inner(within))])
+    }
+}
+
+type Renderer = Box<dyn Fn(&Rect, &mut Buffer)>;
+
+/// Roughly how many times taller a terminal cell is than it is wide.
+const CELL_ASPECT: usize = 2;
+
+/// Keeps a view at a ratio of width to height, e.g. an image preview in a
+/// `Fill` slot. The view is given the largest rect at the ratio which fits,
+/// centred within the space it's given, and layouts size it to the ratio
+/// where they can.
+pub struct AspectRatio {
+    columns: usize,
+    rows: usize,
+    item: Box<dyn View>,
+}
+
+impl AspectRatio {
+    /// A ratio of how the view looks, corrected for cells being about twice
+    /// as tall as they're wide, so `new(1, 1, ..)` is roughly square.
+    pub fn new<V: View>(width: usize, height: usize, item: V) -> Self {
+        Self::cells(width * CELL_ASPECT, height, item)
+    }
+
+    /// A ratio of columns to rows, e.g. for glyphs drawn from whole cells.
+    pub fn cells<V: View>(columns: usize, rows: usize, item: V) -> Self {
+        Self {
+            columns: columns.max(1),
+            rows: rows.max(1),
+            item: Box::new(item),
+        }
+    }
+
+    fn inner(&self, within: &Rect) -> Rect {
+        let dimensions = Constraints::new(Sizing::Fill, Sizing::ratio(self.rows, self.columns))
+            .resolve(&within.dimensions);
+        Rect::new(
+            Point::new(
+                within.origin.x + (within.dimensions.width - dimensions.width) / 2,
+                within.origin.y + (within.dimensions.height - dimensions.height) / 2,
+            ),
+            dimensions,
+        )
+    }
+}
+
+impl View for AspectRatio {
+    fn sizing(&self, bounds: &Dimensions) -> Constraints {
+        let constraints = self.item.sizing(bounds);
+        match (constraints.width, constraints.height) {
+            (Sizing::Fixed(width), _) => Constraints::new(
+                Sizing::Fixed(width),
+                Sizing::Fixed(((width * self.rows + self.columns / 2) / self.columns).max(1)),
+            ),
+            (_, Sizing::Fixed(height)) => Constraints::new(
+                Sizing::Fixed(((height * self.columns + self.rows / 2) / self.rows).max(1)),
+                Sizing::Fixed(height),
+            ),
+            (width, _) => Constraints::new(width, Sizing::ratio(self.rows, self.columns)),
+        }
+    }
+
+    fn render(&self, within: &Rect, buffer: &mut Buffer) {
+        self.item.render(&self.inner(within), buffer);
+    }
+
+    fn inspect(&self, within: &Rect) -> LayoutNode {
+        LayoutNode::of(self, within).children(vec![self.item.inspect(&self.inner(within))])
     }
 }
 
@@ -363,7 +465,7 @@ impl View for Border {
 pub struct Draw {
     width: Sizing,
     height: Sizing,
-    renderer: Box<dyn Fn(&Rect, &mut Buffer)>,
+    renderer: Renderer,
 }
 
 impl Draw {
@@ -383,14 +485,8 @@ impl Draw {
 impl View for Draw {
     fn sizing(&self, bounds: &Dimensions) -> Constraints {
         Constraints::new(
-            match self.width {
-                Sizing::Fill => Sizing::Fill,
-                Sizing::Fixed(n) => Sizing::Fixed(n.clamp(0, bounds.width)),
-            },
-            match self.height {
-                Sizing::Fill => Sizing::Fill,
-                Sizing::Fixed(n) => Sizing::Fixed(n.clamp(0, bounds.height)),
-            },
+            self.width.constrain_by(bounds.width),
+            self.height.constrain_by(bounds.height),
         )
     }
 
@@ -398,3 +494,106 @@ impl View for Draw {
         (self.renderer)(within, buffer);
     }
 }
+
+#[cfg(test)]
+mod text_view_tests {
+    use super::*;
+    use crate::layouts::Auto;
+    use crate::testing::TestTerminal;
+    use crate::text::Wrap;
+
+    #[test]
+    fn rich_label_inside_styled() {
+        let bold = Style::new().bold();
+        let background = Style::new().background(Color::Blue);
+        let both = Style::new().background(Color::Blue).bold();
+        TestTerminal::new(8, 1)
+            .render(
+                &Styled::new(Label::new(Line::new().raw("a ").styled("match", bold)))
+                    .background(Color::Blue),
+            )
+            .assert_text("a match")
+            .assert_styles("bbBBBBBb", &[('b', background), ('B', both)]);
+    }
+
+    #[test]
+    fn wrapped_text_pushes_siblings_down() {
+        TestTerminal::new(12, 5)
+            .render(
+                &Auto::vertical()
+                    .push(
+                        MultilineText::new("help text which flows onto new lines").wrap(Wrap::Word),
+                    )
+                    .push(Label::new("after")),
+            )
+            .assert_text(
+                "
+help text
+which flows
+onto new
+lines
+after",
+            );
+    }
+
+    #[test]
+    fn wraps_within_max_width() {
+        let text = MultilineText::new("one two three")
+            .wrap(Wrap::Word)
+            .width(ContainerSizing::HugBounded { min: 0, max: 5 });
+        assert_eq!(
+            Constraints::new(Sizing::Fixed(5), Sizing::Fixed(3)),
+            text.sizing(&Dimensions::new(20, 10))
+        );
+    }
+}
+
+#[cfg(test)]
+mod aspect_ratio_tests {
+    use super::*;
+    use crate::layouts::{Auto, PinBoard, PinOrigin};
+    use crate::testing::TestTerminal;
+
+    fn square() -> AspectRatio {
+        AspectRatio::new(1, 1, Border::new(Stroke::Solid, Spacer::new()))
+    }
+
+    #[test]
+    fn fits_within_pin() {
+        TestTerminal::new(12, 4)
+            .render(&PinBoard::new(Sizing::Fill, Sizing::Fill).push(PinOrigin::Center, square()))
+            .assert_text(
+                "
+  ┌──────┐
+  │      │
+  │      │
+  └──────┘",
+            );
+    }
+
+    #[test]
+    fn rounds_fixed_sizes() {
+        let bounds = Dimensions::new(10, 10);
+        assert_eq!(
+            AspectRatio::new(1, 1, Label::new("a")).sizing(&bounds),
+            Constraints::new(Sizing::Fixed(1), Sizing::Fixed(1))
+        );
+        assert_eq!(
+            AspectRatio::new(1, 1, Label::new("abc")).sizing(&bounds),
+            Constraints::new(Sizing::Fixed(3), Sizing::Fixed(2))
+        );
+    }
+
+    #[test]
+    fn sized_by_auto() {
+        TestTerminal::new(6, 6)
+            .render(&Auto::vertical().push(square()).push(Label::new("below")))
+            .assert_text(
+                "
+┌────┐
+│    │
+└────┘
+below",
+            );
+    }
+}